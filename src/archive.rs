@@ -0,0 +1,240 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Tar,
+    Zip,
+}
+
+impl std::fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Tar => "tar",
+            Self::Zip => "zip",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for ArchiveFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tar" => Ok(Self::Tar),
+            "zip" => Ok(Self::Zip),
+            value => Err(format!("unsupported archive format '{}'", value)),
+        }
+    }
+}
+
+/// Archive written as files are appended, without seeking, so that it can be streamed to stdout.
+/// Entries have no modification date so that archiving the same files gives the same bytes.
+pub enum Archive<W: std::io::Write> {
+    Tar(tar::Builder<W>),
+    Zip(Box<zip::ZipWriter<zip::write::StreamWriter<W>>>),
+}
+
+fn zip_error(err: zip::result::ZipError) -> crate::error::Error {
+    match err {
+        zip::result::ZipError::Io(err) => err.into(),
+        err => crate::error::Error::generic(err.to_string()),
+    }
+}
+
+impl<W: std::io::Write> Archive<W> {
+    pub fn new(w: W, format: ArchiveFormat) -> Self {
+        match format {
+            ArchiveFormat::Tar => {
+                let mut builder = tar::Builder::new(w);
+                builder.mode(tar::HeaderMode::Deterministic);
+                Self::Tar(builder)
+            }
+            ArchiveFormat::Zip => Self::Zip(Box::new(zip::ZipWriter::new_stream(w))),
+        }
+    }
+
+    pub fn append(&mut self, path: &str, data: &[u8]) -> Result<(), crate::error::Error> {
+        match self {
+            Self::Tar(builder) => {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_mode(0o644);
+                header.set_mtime(0);
+                builder.append_data(&mut header, path, data)?;
+            }
+            Self::Zip(zip) => {
+                // ZIP64 records are added by the writer when the archive holds more than 65535
+                // files, but files larger than 4GiB must be announced before being written
+                let method = if data.is_empty() {
+                    zip::CompressionMethod::Stored
+                } else {
+                    zip::CompressionMethod::Deflated
+                };
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(method)
+                    .last_modified_time(zip::DateTime::default())
+                    .unix_permissions(0o644)
+                    .large_file(data.len() as u64 >= u32::MAX as u64);
+                zip.start_file(path, options).map_err(zip_error)?;
+                std::io::Write::write_all(zip.as_mut(), data)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the end of the archive and returns the underlying writer.
+    pub fn finish(self) -> Result<W, crate::error::Error> {
+        let mut w = match self {
+            Self::Tar(builder) => builder.into_inner()?,
+            Self::Zip(zip) => zip.finish().map_err(zip_error)?.into_inner(),
+        };
+        w.flush()?;
+
+        Ok(w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    #[test]
+    fn archive_format_from_string() {
+        assert_eq!(
+            Ok(super::ArchiveFormat::Tar),
+            super::ArchiveFormat::from_str("tar")
+        );
+        assert_eq!(
+            Ok(super::ArchiveFormat::Zip),
+            super::ArchiveFormat::from_str("zip")
+        );
+        assert_eq!(
+            Err("unsupported archive format 'rar'".to_string()),
+            super::ArchiveFormat::from_str("rar")
+        );
+    }
+
+    #[test]
+    fn archive_format_display() {
+        assert_eq!("tar", format!("{}", super::ArchiveFormat::Tar));
+        assert_eq!("zip", format!("{}", super::ArchiveFormat::Zip));
+    }
+
+    #[test]
+    fn archive_tar() {
+        let mut archive = super::Archive::new(Vec::new(), super::ArchiveFormat::Tar);
+        archive
+            .append("avatars/hello.txt", b"hello")
+            .expect("failed to append file");
+        archive
+            .append("manifest.csv", b"word\nhello\n")
+            .expect("failed to append file");
+        let bytes = archive.finish().expect("failed to finish archive");
+
+        let mut reader = tar::Archive::new(bytes.as_slice());
+        let files: Vec<(String, String)> = reader
+            .entries()
+            .expect("failed to read archive")
+            .map(|entry| {
+                let mut entry = entry.expect("failed to read entry");
+                let path = entry
+                    .path()
+                    .expect("failed to read path")
+                    .display()
+                    .to_string();
+                let mut content = String::new();
+                std::io::Read::read_to_string(&mut entry, &mut content)
+                    .expect("failed to read content");
+                (path, content)
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("avatars/hello.txt".to_string(), "hello".to_string()),
+                ("manifest.csv".to_string(), "word\nhello\n".to_string()),
+            ],
+            files
+        );
+    }
+
+    // files of a zip archive, read back by a reader which supports seeking
+    fn zip_files(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let mut reader =
+            zip::ZipArchive::new(std::io::Cursor::new(bytes)).expect("failed to read archive");
+
+        (0..reader.len())
+            .map(|index| {
+                let mut file = reader.by_index(index).expect("failed to read entry");
+                let mut content = Vec::new();
+                std::io::Read::read_to_end(&mut file, &mut content)
+                    .expect("failed to read content");
+                assert_eq!(Some(0o100644), file.unix_mode());
+
+                (file.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn archive_zip() {
+        let mut archive = super::Archive::new(Vec::new(), super::ArchiveFormat::Zip);
+        archive
+            .append("avatars/hello.txt", "hello ".repeat(10).as_bytes())
+            .expect("failed to append file");
+        archive
+            .append("manifest.csv", b"word\nhello\n")
+            .expect("failed to append file");
+        archive
+            .append("empty.txt", b"")
+            .expect("failed to append file");
+        let bytes = archive.finish().expect("failed to finish archive");
+
+        assert_eq!(
+            vec![
+                (
+                    "avatars/hello.txt".to_string(),
+                    "hello ".repeat(10).into_bytes()
+                ),
+                ("manifest.csv".to_string(), b"word\nhello\n".to_vec()),
+                ("empty.txt".to_string(), Vec::new()),
+            ],
+            zip_files(&bytes)
+        );
+    }
+
+    #[test]
+    fn archive_zip_is_deterministic() {
+        let archive = || {
+            let mut archive = super::Archive::new(Vec::new(), super::ArchiveFormat::Zip);
+            archive
+                .append("hello.txt", b"hello")
+                .expect("failed to append file");
+            archive.finish().expect("failed to finish archive")
+        };
+
+        assert_eq!(archive(), archive());
+    }
+
+    #[test]
+    fn archive_zip64_when_many_files() {
+        let mut archive = super::Archive::new(Vec::new(), super::ArchiveFormat::Zip);
+        (0..70_000).for_each(|index| {
+            archive
+                .append(&format!("{}", index), b"")
+                .expect("failed to append file")
+        });
+        let bytes = archive.finish().expect("failed to finish archive");
+
+        // the count of the end of central directory overflows, so that readers must use the
+        // ZIP64 record
+        let end = bytes.len() - 22;
+        assert_eq!(0x06054b50u32.to_le_bytes(), bytes[end..end + 4]);
+        assert_eq!(0xffffu16.to_le_bytes(), bytes[end + 10..end + 12]);
+
+        let files = zip_files(&bytes);
+        assert_eq!(70_000, files.len());
+        assert_eq!(("69999".to_string(), Vec::new()), files[69_999]);
+    }
+}
@@ -0,0 +1,878 @@
+use sha2::Digest;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    /// One word per line.
+    Lines,
+    /// Comma separated values with a header row.
+    Csv,
+    /// One JSON document per line.
+    Jsonl,
+}
+
+impl std::fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Lines => "lines",
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(Self::Lines),
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::Jsonl),
+            value => Err(format!("unsupported input format '{}'", value)),
+        }
+    }
+}
+
+/// Reads the words of a batch. `column` selects the column of a CSV input by its header, or the
+/// field of the objects of a JSONL input. Without it, the first column of a CSV input is used and
+/// every line of a JSONL input must be a string. Empty words are skipped.
+pub fn read_words<R: std::io::Read>(
+    reader: R,
+    format: InputFormat,
+    column: Option<&str>,
+) -> Result<Vec<String>, crate::error::Error> {
+    match format {
+        InputFormat::Lines => {
+            if column.is_some() {
+                return Err(crate::error::Error::generic(
+                    "a column can only be selected in csv and jsonl inputs".to_string(),
+                ));
+            }
+
+            std::io::BufRead::lines(std::io::BufReader::new(reader))
+                .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+                .map(|line| Ok(line?.trim_end_matches('\r').to_string()))
+                .collect()
+        }
+        InputFormat::Csv => read_csv(reader, column),
+        InputFormat::Jsonl => read_jsonl(reader, column),
+    }
+}
+
+fn read_csv<R: std::io::Read>(
+    reader: R,
+    column: Option<&str>,
+) -> Result<Vec<String>, crate::error::Error> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let index = match column {
+        Some(column) => reader
+            .headers()
+            .map_err(csv_error)?
+            .iter()
+            .position(|header| header == column)
+            .ok_or_else(|| {
+                crate::error::Error::generic(format!("no column '{}' in the csv header", column))
+            })?,
+        None => 0,
+    };
+
+    let mut words = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        let word = record.get(index).ok_or_else(|| {
+            crate::error::Error::generic(format!("line {}: missing column {}", line, index + 1))
+        })?;
+
+        if !word.is_empty() {
+            words.push(word.to_string());
+        }
+    }
+
+    Ok(words)
+}
+
+fn csv_error(err: csv::Error) -> crate::error::Error {
+    let msg = err.to_string();
+
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => err.into(),
+        _ => crate::error::Error::generic(msg),
+    }
+}
+
+fn read_jsonl<R: std::io::Read>(
+    reader: R,
+    column: Option<&str>,
+) -> Result<Vec<String>, crate::error::Error> {
+    let mut words = Vec::new();
+    for (index, line) in std::io::BufRead::lines(std::io::BufReader::new(reader)).enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let error =
+            |msg: String| crate::error::Error::generic(format!("line {}: {}", index + 1, msg));
+        let document: serde_json::Value =
+            serde_json::from_str(&line).map_err(|e| error(e.to_string()))?;
+        let value = match column {
+            Some(column) => document
+                .get(column)
+                .ok_or_else(|| error(format!("no field '{}'", column)))?,
+            None => &document,
+        };
+        let word = match value {
+            serde_json::Value::String(value) => value.clone(),
+            serde_json::Value::Number(value) => value.to_string(),
+            _ => return Err(error("expected a string or a number".to_string())),
+        };
+
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+
+    Ok(words)
+}
+
+/// Lowercase version of the word where every run of characters other than ASCII letters and
+/// digits is replaced by a dash.
+pub fn slug(word: &str) -> String {
+    word.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Word,
+    Slug,
+    Hash,
+    Extension,
+}
+
+/// Path of the file generated for a word, where `{word}`, `{slug}`, `{hash}` and `{ext}` are
+/// replaced by the word, its slug, the hex digest of the seed and the extension of the output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl std::str::FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut rest = s;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Text(rest[..start].to_string()));
+            }
+
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| format!("unclosed placeholder in '{}'", s))?;
+            let part = match &rest[start + 1..start + end] {
+                "word" => Part::Word,
+                "slug" => Part::Slug,
+                "hash" => Part::Hash,
+                "ext" => Part::Extension,
+                value => return Err(format!("unsupported placeholder '{{{}}}'", value)),
+            };
+            parts.push(part);
+            rest = &rest[start + end + 1..];
+        }
+
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest.to_string()));
+        }
+
+        Ok(Self { parts })
+    }
+}
+
+impl Template {
+    /// Path for the given word. Path separators of the word are replaced by underscores, and the
+    /// slug falls back to the first 16 characters of the hash when it would be empty.
+    pub fn expand(&self, word: &str, hash: &str, extension: &str) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.clone(),
+                Part::Word => word.replace(['/', '\\'], "_"),
+                Part::Slug => match slug(word) {
+                    slug if slug.is_empty() => hash.chars().take(16).collect(),
+                    slug => slug,
+                },
+                Part::Hash => hash.to_string(),
+                Part::Extension => extension.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Applies `f` to every item using up to `jobs` threads. Results are returned in the order of the
+/// items, whatever the order in which they are processed.
+pub fn parallel_map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let results: Vec<std::sync::Mutex<Option<R>>> =
+        items.iter().map(|_| std::sync::Mutex::new(None)).collect();
+
+    std::thread::scope(|scope| {
+        (0..jobs.clamp(1, items.len().max(1))).for_each(|_| {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+
+                let result = f(item);
+                *results[index].lock().expect("failed to lock result") = Some(result);
+            });
+        })
+    });
+
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .expect("failed to unlock result")
+                .expect("every item is processed")
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Csv,
+    Json,
+}
+
+impl std::fmt::Display for ManifestFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for ManifestFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            value => Err(format!("unsupported manifest format '{}'", value)),
+        }
+    }
+}
+
+/// Image generated for a word of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub word: String,
+    pub path: String,
+    pub hash: String,
+    pub color: crate::generator::Color,
+    pub cells: Vec<bool>,
+    /// Reason why the image could not be generated, if any.
+    pub error: Option<String>,
+}
+
+/// Two entries, given by their indexes, whose images have the same color and differ by
+/// `distance` cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collision {
+    pub first: usize,
+    pub second: usize,
+    pub distance: usize,
+}
+
+/// Finds the pairs of entries of the same color whose grids differ by at most `max_distance`
+/// cells, sorted by indexes.
+///
+/// Grids are split in `max_distance + 1` bands: two grids within the distance have at least one
+/// identical band, so only the entries sharing a band are compared.
+pub fn collisions(entries: &[Entry], max_distance: usize) -> Vec<Collision> {
+    let cells = entries.first().map_or(0, |entry| entry.cells.len());
+    let bands = (max_distance + 1).min(cells + 1);
+
+    let mut buckets = std::collections::HashMap::new();
+    entries.iter().enumerate().for_each(|(index, entry)| {
+        (0..bands).for_each(|band| {
+            let range = (band * cells / bands)..((band + 1) * cells / bands);
+            buckets
+                .entry((entry.color, band, &entry.cells[range]))
+                .or_insert_with(Vec::new)
+                .push(index)
+        })
+    });
+
+    let mut candidates = std::collections::BTreeSet::new();
+    buckets.values().for_each(|indexes| {
+        indexes.iter().enumerate().for_each(|(position, first)| {
+            indexes[position + 1..].iter().for_each(|second| {
+                candidates.insert((*first, *second));
+            })
+        })
+    });
+
+    candidates
+        .into_iter()
+        .filter_map(|(first, second)| {
+            let distance = entries[first]
+                .cells
+                .iter()
+                .zip(&entries[second].cells)
+                .filter(|(a, b)| a != b)
+                .count();
+
+            (distance <= max_distance).then_some(Collision {
+                first,
+                second,
+                distance,
+            })
+        })
+        .collect()
+}
+
+/// Writes the list of entries, with the algorithm version and the words each entry collides
+/// with.
+pub fn write_manifest<W: std::io::Write>(
+    mut w: W,
+    format: ManifestFormat,
+    entries: &[Entry],
+    collisions: &[Collision],
+) -> Result<(), crate::error::Error> {
+    let mut colliding: Vec<Vec<(usize, usize)>> = vec![Vec::new(); entries.len()];
+    collisions.iter().for_each(|collision| {
+        colliding[collision.first].push((collision.second, collision.distance));
+        colliding[collision.second].push((collision.first, collision.distance));
+    });
+    colliding.iter_mut().for_each(|others| others.sort());
+
+    match format {
+        ManifestFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(w);
+            writer
+                .write_record([
+                    "word",
+                    "path",
+                    "hash",
+                    "color",
+                    "version",
+                    "error",
+                    "collisions",
+                ])
+                .map_err(csv_error)?;
+
+            entries
+                .iter()
+                .zip(&colliding)
+                .try_for_each(|(entry, others)| {
+                    let others: Vec<&str> = others
+                        .iter()
+                        .map(|(other, _)| entries[*other].word.as_str())
+                        .collect();
+
+                    writer.write_record([
+                        entry.word.as_str(),
+                        entry.path.as_str(),
+                        entry.hash.as_str(),
+                        entry.color.to_string().as_str(),
+                        crate::generator::ALGORITHM_VERSION.to_string().as_str(),
+                        entry.error.as_deref().unwrap_or_default(),
+                        others.join(";").as_str(),
+                    ])
+                })
+                .map_err(csv_error)?;
+
+            writer.flush()?;
+        }
+        ManifestFormat::Json => {
+            let document: Vec<serde_json::Value> = entries
+                .iter()
+                .zip(&colliding)
+                .map(|(entry, others)| {
+                    let others: Vec<serde_json::Value> = others
+                        .iter()
+                        .map(|(other, distance)| {
+                            serde_json::json!({
+                                "word": entries[*other].word,
+                                "distance": distance,
+                            })
+                        })
+                        .collect();
+
+                    serde_json::json!({
+                        "word": entry.word,
+                        "path": entry.path,
+                        "hash": entry.hash,
+                        "color": entry.color.to_string(),
+                        "version": crate::generator::ALGORITHM_VERSION,
+                        "error": entry.error,
+                        "collisions": others,
+                    })
+                })
+                .collect();
+
+            serde_json::to_writer_pretty(&mut w, &document).map_err(std::io::Error::from)?;
+            writeln!(w)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Directory holding images already generated, addressed by a fingerprint of everything their
+/// content depends on: the algorithm version, the render options and the hash of the seed.
+pub struct Cache {
+    directory: std::path::PathBuf,
+    options: String,
+}
+
+impl Cache {
+    /// `options` describes the render options, any change of it invalidates the whole cache.
+    pub fn new<P: AsRef<std::path::Path>>(directory: P, options: String) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+            options,
+        }
+    }
+
+    pub fn fingerprint(&self, hash: &str) -> String {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(format!(
+            "{}\n{}\n{}",
+            crate::generator::ALGORITHM_VERSION,
+            self.options,
+            hash
+        ));
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn path(&self, fingerprint: &str) -> std::path::PathBuf {
+        self.directory.join(&fingerprint[..2]).join(fingerprint)
+    }
+
+    pub fn get(&self, fingerprint: &str) -> Result<Option<Vec<u8>>, crate::error::Error> {
+        let path = self.path(fingerprint);
+
+        match std::fs::read(&path) {
+            Ok(content) => Ok(Some(content)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(crate::error::Error::from(err)
+                .context(format!("cannot read cache \"{}\"", path.display()))),
+        }
+    }
+
+    pub fn put(&self, fingerprint: &str, content: &[u8]) -> Result<(), crate::error::Error> {
+        let path = self.path(fingerprint);
+        // written next to its final path then renamed, so that an interrupted run never leaves a
+        // truncated image in the cache
+        let temporary = path.with_extension(uuid::Uuid::new_v4().to_string());
+
+        std::fs::create_dir_all(path.parent().unwrap_or(&self.directory))
+            .and_then(|_| std::fs::write(&temporary, content))
+            .and_then(|_| std::fs::rename(&temporary, &path))
+            .map_err(|e| {
+                crate::error::Error::from(e)
+                    .context(format!("cannot write cache \"{}\"", path.display()))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    #[test]
+    fn input_format_from_string() {
+        assert_eq!(
+            Ok(super::InputFormat::Lines),
+            super::InputFormat::from_str("lines")
+        );
+        assert_eq!(
+            Ok(super::InputFormat::Csv),
+            super::InputFormat::from_str("csv")
+        );
+        assert_eq!(
+            Ok(super::InputFormat::Jsonl),
+            super::InputFormat::from_str("jsonl")
+        );
+        assert_eq!(
+            Err("unsupported input format 'xml'".to_string()),
+            super::InputFormat::from_str("xml")
+        );
+    }
+
+    #[test]
+    fn input_format_display() {
+        assert_eq!("lines", format!("{}", super::InputFormat::Lines));
+        assert_eq!("csv", format!("{}", super::InputFormat::Csv));
+        assert_eq!("jsonl", format!("{}", super::InputFormat::Jsonl));
+    }
+
+    #[test]
+    fn read_words_lines() {
+        let input = "hello\r\nhello world\n\n  \nfoo\n";
+
+        let words = super::read_words(input.as_bytes(), super::InputFormat::Lines, None)
+            .expect("failed to read words");
+
+        assert_eq!(vec!["hello", "hello world", "foo"], words);
+    }
+
+    #[test]
+    fn read_words_lines_with_column() {
+        let err = super::read_words("hello".as_bytes(), super::InputFormat::Lines, Some("name"))
+            .expect_err("column should be rejected");
+
+        assert_eq!(
+            "a column can only be selected in csv and jsonl inputs",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn read_words_csv() {
+        let input = "id,email\n1,alice@example.com\n2,\"bob, jr@example.com\"\n3,\n";
+
+        let first = super::read_words(input.as_bytes(), super::InputFormat::Csv, None)
+            .expect("failed to read words");
+        let email = super::read_words(input.as_bytes(), super::InputFormat::Csv, Some("email"))
+            .expect("failed to read words");
+
+        assert_eq!(vec!["1", "2", "3"], first);
+        assert_eq!(vec!["alice@example.com", "bob, jr@example.com"], email);
+    }
+
+    #[test]
+    fn read_words_csv_unknown_column() {
+        let err = super::read_words("id\n1\n".as_bytes(), super::InputFormat::Csv, Some("name"))
+            .expect_err("unknown column should be rejected");
+
+        assert_eq!("no column 'name' in the csv header", err.to_string());
+    }
+
+    #[test]
+    fn read_words_csv_missing_column() {
+        let input = "id,email\n1,alice@example.com\n2\n";
+
+        let err = super::read_words(input.as_bytes(), super::InputFormat::Csv, Some("email"))
+            .expect_err("missing column should be rejected");
+
+        assert_eq!("line 3: missing column 2", err.to_string());
+    }
+
+    #[test]
+    fn read_words_jsonl() {
+        let input = "\"hello\"\n\n42\n";
+        let objects = "{\"id\": 1, \"name\": \"alice\"}\n{\"id\": 2, \"name\": \"bob\"}\n";
+
+        let words = super::read_words(input.as_bytes(), super::InputFormat::Jsonl, None)
+            .expect("failed to read words");
+        let names = super::read_words(objects.as_bytes(), super::InputFormat::Jsonl, Some("name"))
+            .expect("failed to read words");
+        let ids = super::read_words(objects.as_bytes(), super::InputFormat::Jsonl, Some("id"))
+            .expect("failed to read words");
+
+        assert_eq!(vec!["hello", "42"], words);
+        assert_eq!(vec!["alice", "bob"], names);
+        assert_eq!(vec!["1", "2"], ids);
+    }
+
+    #[test]
+    fn read_words_jsonl_errors() {
+        let missing = super::read_words(
+            "{\"id\": 1}\n{\"name\": \"bob\"}".as_bytes(),
+            super::InputFormat::Jsonl,
+            Some("id"),
+        )
+        .expect_err("missing field should be rejected");
+        let object = super::read_words("{\"id\": 1}".as_bytes(), super::InputFormat::Jsonl, None)
+            .expect_err("object should be rejected");
+        let invalid = super::read_words("\"a\"\n{".as_bytes(), super::InputFormat::Jsonl, None)
+            .expect_err("invalid json should be rejected");
+
+        assert_eq!("line 2: no field 'id'", missing.to_string());
+        assert_eq!("line 1: expected a string or a number", object.to_string());
+        assert!(invalid.to_string().starts_with("line 2: "));
+    }
+
+    #[test]
+    fn parallel_map_keeps_order() {
+        let items: Vec<u64> = (0..100).collect();
+
+        let results = super::parallel_map(&items, 8, |item| {
+            // later items finish first
+            std::thread::sleep(std::time::Duration::from_micros(100 - item));
+            item * 2
+        });
+
+        assert_eq!(
+            items.iter().map(|item| item * 2).collect::<Vec<_>>(),
+            results
+        );
+    }
+
+    #[test]
+    fn parallel_map_more_jobs_than_items() {
+        assert_eq!(
+            vec![2, 4],
+            super::parallel_map(&[1, 2], 16, |item| item * 2)
+        );
+        assert_eq!(
+            Vec::<i32>::new(),
+            super::parallel_map(&[], 16, |item: &i32| item * 2)
+        );
+    }
+
+    fn entry(word: &str, color: crate::generator::Color, cells: &str) -> super::Entry {
+        super::Entry {
+            word: word.to_string(),
+            path: format!("{}.png", word),
+            hash: format!("{}-hash", word),
+            color,
+            cells: cells.chars().map(|c| c == '1').collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn manifest_format_from_string() {
+        assert_eq!(
+            Ok(super::ManifestFormat::Csv),
+            super::ManifestFormat::from_str("csv")
+        );
+        assert_eq!(
+            Ok(super::ManifestFormat::Json),
+            super::ManifestFormat::from_str("json")
+        );
+        assert_eq!(
+            Err("unsupported manifest format 'xml'".to_string()),
+            super::ManifestFormat::from_str("xml")
+        );
+    }
+
+    #[test]
+    fn manifest_format_display() {
+        assert_eq!("csv", format!("{}", super::ManifestFormat::Csv));
+        assert_eq!("json", format!("{}", super::ManifestFormat::Json));
+    }
+
+    #[test]
+    fn collisions() {
+        use crate::generator::Color;
+
+        let entries = vec![
+            entry("a", Color::Red, "1100110011"),
+            entry("b", Color::Red, "1100110011"),
+            entry("c", Color::Blue, "1100110011"),
+            entry("d", Color::Red, "1100110000"),
+            entry("e", Color::Red, "0011001100"),
+        ];
+
+        let exact = super::collisions(&entries, 0);
+        let near = super::collisions(&entries, 2);
+        let all = super::collisions(&entries, 10);
+
+        let collision = |first, second, distance| super::Collision {
+            first,
+            second,
+            distance,
+        };
+        assert_eq!(vec![collision(0, 1, 0)], exact);
+        assert_eq!(
+            vec![collision(0, 1, 0), collision(0, 3, 2), collision(1, 3, 2)],
+            near
+        );
+        assert_eq!(6, all.len());
+        assert!(!all.iter().any(|c| c.first == 2 || c.second == 2));
+    }
+
+    #[test]
+    fn collisions_empty() {
+        assert_eq!(Vec::<super::Collision>::new(), super::collisions(&[], 2));
+    }
+
+    #[test]
+    fn write_manifest_csv() {
+        use crate::generator::Color;
+
+        let mut entries = vec![
+            entry("a", Color::Red, "1100"),
+            entry("b, c", Color::Red, "1100"),
+            entry("d", Color::Red, "1101"),
+        ];
+        entries[2].error = Some("cannot open \"d.png\"".to_string());
+        let collisions = super::collisions(&entries, 1);
+        let mut buffer = Vec::new();
+
+        super::write_manifest(
+            &mut buffer,
+            super::ManifestFormat::Csv,
+            &entries,
+            &collisions,
+        )
+        .expect("failed to write manifest");
+
+        let expect = "word,path,hash,color,version,error,collisions\n\
+            a,a.png,a-hash,red,1,,\"b, c;d\"\n\
+            \"b, c\",\"b, c.png\",\"b, c-hash\",red,1,,a;d\n\
+            d,d.png,d-hash,red,1,\"cannot open \"\"d.png\"\"\",\"a;b, c\"\n";
+        assert_eq!(expect, String::from_utf8(buffer).expect("invalid utf-8"));
+    }
+
+    #[test]
+    fn write_manifest_json() {
+        use crate::generator::Color;
+
+        let entries = vec![
+            entry("a", Color::Red, "1100"),
+            entry("b", Color::Blue, "1100"),
+            entry("c", Color::Red, "1101"),
+        ];
+        let collisions = super::collisions(&entries, 1);
+        let mut buffer = Vec::new();
+
+        super::write_manifest(
+            &mut buffer,
+            super::ManifestFormat::Json,
+            &entries,
+            &collisions,
+        )
+        .expect("failed to write manifest");
+
+        let document: serde_json::Value =
+            serde_json::from_slice(&buffer).expect("failed to parse manifest");
+        assert_eq!(
+            serde_json::json!([
+                {
+                    "word": "a",
+                    "path": "a.png",
+                    "hash": "a-hash",
+                    "color": "red",
+                    "version": crate::generator::ALGORITHM_VERSION,
+                    "error": null,
+                    "collisions": [{"word": "c", "distance": 1}],
+                },
+                {
+                    "word": "b",
+                    "path": "b.png",
+                    "hash": "b-hash",
+                    "color": "blue",
+                    "version": crate::generator::ALGORITHM_VERSION,
+                    "error": null,
+                    "collisions": [],
+                },
+                {
+                    "word": "c",
+                    "path": "c.png",
+                    "hash": "c-hash",
+                    "color": "red",
+                    "version": crate::generator::ALGORITHM_VERSION,
+                    "error": null,
+                    "collisions": [{"word": "a", "distance": 1}],
+                },
+            ]),
+            document
+        );
+    }
+
+    #[test]
+    fn cache_fingerprint() {
+        let cache = super::Cache::new("cache", "png".to_string());
+        let other = super::Cache::new("cache", "svg".to_string());
+
+        let fingerprint = cache.fingerprint("0123abcd");
+
+        assert_eq!(64, fingerprint.len());
+        assert_eq!(fingerprint, cache.fingerprint("0123abcd"));
+        assert_ne!(fingerprint, cache.fingerprint("0123abce"));
+        assert_ne!(fingerprint, other.fingerprint("0123abcd"));
+    }
+
+    #[test]
+    fn cache_get_put() {
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let cache = super::Cache::new(&directory, "png".to_string());
+        let fingerprint = cache.fingerprint("0123abcd");
+
+        assert_eq!(None, cache.get(&fingerprint).expect("failed to read cache"));
+        cache
+            .put(&fingerprint, b"image")
+            .expect("failed to write cache");
+        assert_eq!(
+            Some(b"image".to_vec()),
+            cache.get(&fingerprint).expect("failed to read cache")
+        );
+        assert_eq!(
+            1,
+            std::fs::read_dir(directory.join(&fingerprint[..2]))
+                .expect("failed to list cache")
+                .count()
+        );
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn slug() {
+        assert_eq!("hello-world", super::slug("Hello, World!"));
+        assert_eq!("alice-example-com", super::slug("alice@example.com"));
+        assert_eq!("caf", super::slug("café"));
+        assert_eq!("", super::slug("こんにちは"));
+    }
+
+    #[test]
+    fn template_from_string() {
+        assert_eq!(
+            Err("unsupported placeholder '{name}'".to_string()),
+            super::Template::from_str("avatars/{name}.png")
+        );
+        assert_eq!(
+            Err("unclosed placeholder in 'avatars/{slug'".to_string()),
+            super::Template::from_str("avatars/{slug")
+        );
+    }
+
+    #[test]
+    fn template_expand() {
+        let template = super::Template::from_str("avatars/{slug}-{hash}.{ext}")
+            .expect("failed to parse template");
+        let word = super::Template::from_str("{word}").expect("failed to parse template");
+
+        assert_eq!(
+            "avatars/hello-world-0123abcd.png",
+            template.expand("Hello World", "0123abcd", "png")
+        );
+        assert_eq!(
+            "avatars/0123456789abcdef-0123456789abcdef01.svg",
+            template.expand("こんにちは", "0123456789abcdef01", "svg")
+        );
+        assert_eq!("a_b_c", word.expand("a/b\\c", "0123abcd", "png"));
+    }
+}
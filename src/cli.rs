@@ -3,14 +3,56 @@ use std::os::fd::FromRawFd;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CliOutput {
     Terminal,
+    Emoji,
+    Html,
+    Css,
+    Json,
+    Pbm,
+    Ppm,
+    Xpm,
+    Svg,
+    Ico,
+    Favicon,
+    Pdf,
+    Eps,
+    Stl,
+    Obj,
+    CrossStitch,
+    Beads,
     Png,
+    Jpeg,
+    WebP,
+    Bmp,
+    Gif,
+    Tiff,
 }
 
 impl std::fmt::Display for CliOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let value = match self {
             Self::Terminal => "term",
+            Self::Emoji => "emoji",
+            Self::Html => "html",
+            Self::Css => "css",
+            Self::Json => "json",
+            Self::Pbm => "pbm",
+            Self::Ppm => "ppm",
+            Self::Xpm => "xpm",
+            Self::Svg => "svg",
+            Self::Ico => "ico",
+            Self::Favicon => "favicon",
+            Self::Pdf => "pdf",
+            Self::Eps => "eps",
+            Self::Stl => "stl",
+            Self::Obj => "obj",
+            Self::CrossStitch => "cross-stitch",
+            Self::Beads => "beads",
             Self::Png => "png",
+            Self::Jpeg => "jpeg",
+            Self::WebP => "webp",
+            Self::Bmp => "bmp",
+            Self::Gif => "gif",
+            Self::Tiff => "tiff",
         };
 
         write!(f, "{}", value)
@@ -23,20 +65,183 @@ impl std::str::FromStr for CliOutput {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "term" => Ok(Self::Terminal),
+            "emoji" => Ok(Self::Emoji),
+            "html" => Ok(Self::Html),
+            "css" => Ok(Self::Css),
+            "json" => Ok(Self::Json),
+            "pbm" => Ok(Self::Pbm),
+            "ppm" => Ok(Self::Ppm),
+            "xpm" => Ok(Self::Xpm),
+            "svg" => Ok(Self::Svg),
+            "ico" => Ok(Self::Ico),
+            "favicon" => Ok(Self::Favicon),
+            "pdf" => Ok(Self::Pdf),
+            "eps" => Ok(Self::Eps),
+            "stl" => Ok(Self::Stl),
+            "obj" => Ok(Self::Obj),
+            "cross-stitch" => Ok(Self::CrossStitch),
+            "beads" => Ok(Self::Beads),
             "png" => Ok(Self::Png),
+            "jpeg" => Ok(Self::Jpeg),
+            "webp" => Ok(Self::WebP),
+            "bmp" => Ok(Self::Bmp),
+            "gif" => Ok(Self::Gif),
+            "tiff" => Ok(Self::Tiff),
             value => Err(format!("unsupported output format '{}'", value)),
         }
     }
 }
 
-#[derive(clap::Parser)]
-#[command(version, about)]
-pub struct Cli {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CliMask {
+    Square,
+    Circle,
+    Rounded,
+    Squircle,
+}
+
+impl std::fmt::Display for CliMask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Square => "square",
+            Self::Circle => "circle",
+            Self::Rounded => "rounded",
+            Self::Squircle => "squircle",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for CliMask {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "square" => Ok(Self::Square),
+            "circle" => Ok(Self::Circle),
+            "rounded" => Ok(Self::Rounded),
+            "squircle" => Ok(Self::Squircle),
+            value => Err(format!("unsupported mask '{}'", value)),
+        }
+    }
+}
+
+/// Secret key, whose value is never printed.
+#[derive(Clone, PartialEq, Eq)]
+struct CliSecret(Vec<u8>);
+
+impl std::fmt::Debug for CliSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CliSecret(..)")
+    }
+}
+
+impl std::str::FromStr for CliSecret {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("the key must not be empty".to_string());
+        }
+
+        Ok(Self(s.as_bytes().to_vec()))
+    }
+}
+
+// reads a key from a file, without the line ending it usually ends with
+fn read_key_file(path: &str) -> Result<CliSecret, String> {
+    let mut key = std::fs::read(path).map_err(|e| format!("cannot read \"{}\": {}", path, e))?;
+    if key.ends_with(b"\n") {
+        key.pop();
+        if key.ends_with(b"\r") {
+            key.pop();
+        }
+    }
+    if key.is_empty() {
+        return Err(format!("the key of \"{}\" must not be empty", path));
+    }
+
+    Ok(CliSecret(key))
+}
+
+// parses a finite number, which must be accepted by `valid` described by `expect`
+fn parse_f32(value: &str, valid: impl Fn(f32) -> bool, expect: &str) -> Result<f32, String> {
+    let number: f32 = value
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", value))?;
+    if !number.is_finite() || !valid(number) {
+        return Err(format!("must be {}", expect));
+    }
+
+    Ok(number)
+}
+
+fn parse_positive(value: &str) -> Result<f32, String> {
+    parse_f32(value, |number| number > 0.0, "greater than 0")
+}
+
+fn parse_non_negative(value: &str) -> Result<f32, String> {
+    parse_f32(value, |number| number >= 0.0, "greater than or equal to 0")
+}
+
+fn parse_fraction(value: &str) -> Result<f32, String> {
+    parse_f32(
+        value,
+        |number| (0.0..=1.0).contains(&number),
+        "between 0 and 1",
+    )
+}
+
+/// Options controlling how a word is hashed into a seed, shared by every command.
+#[derive(clap::Args, Debug)]
+struct CliHashing {
+    #[arg(
+        long = "hash",
+        default_value_t = crate::generator::HashFunction::Sha256,
+        help = "hash function turning the word into a seed (sha256, sha512, blake3, md5)"
+    )]
+    hash: crate::generator::HashFunction,
+    #[arg(
+        long = "key",
+        env = "PIXIE_KEY",
+        hide_env_values = true,
+        help = "secret key hashing the words with HMAC, or the keyed mode of blake3, so that images cannot be generated without it"
+    )]
+    key: Option<CliSecret>,
+    #[arg(
+        long = "key-file",
+        value_parser = read_key_file,
+        help = "file holding the secret key, used instead of --key and PIXIE_KEY"
+    )]
+    key_file: Option<CliSecret>,
+}
+
+impl CliHashing {
+    fn secret(&self) -> Option<&[u8]> {
+        self.key_file
+            .as_ref()
+            .or(self.key.as_ref())
+            .map(|secret| secret.0.as_slice())
+    }
+
+    fn seed(&self, word: &str) -> crate::generator::Seed {
+        crate::generator::Seed::with_digest(word, self.hash, self.secret())
+    }
+}
+
+/// Version of the description of the options keying the cache of the batch command. It must be
+/// bumped every time the description changes for the same options.
+const FINGERPRINT_VERSION: u32 = 1;
+
+/// Options controlling how the image of a word is generated, shared by every command.
+#[derive(clap::Args, Debug)]
+struct CliOptions {
     #[arg(
         short='o',
         long="output",
         default_value_t = CliOutput::Terminal,
-        help = "format of the generated image (term=ascii characters, png=png file)"
+        help = "format of the generated image (term=ascii characters, emoji=emoji squares, html=html table, css=css box-shadow rule, json=json document, pbm=portable bitmap, ppm=portable pixmap, xpm=x pixmap, svg=svg file, ico=multi-resolution icon, favicon=directory of favicon files, pdf=pdf document, eps=encapsulated postscript, stl=stl 3d model, obj=obj 3d model, cross-stitch=cross-stitch pattern chart, beads=bead pattern chart, png=png file, jpeg=jpeg file, webp=webp file, bmp=bmp file, gif=gif file, tiff=tiff file)"
     )]
     output: CliOutput,
     #[arg(
@@ -46,16 +251,513 @@ pub struct Cli {
         help = "size of the pixel grid"
     )]
     size: usize,
+    #[arg(
+        long = "color-depth",
+        help = "colors used by the term output (16, 256, truecolor), detected from COLORTERM and TERM when omitted"
+    )]
+    color_depth: Option<crate::rendering::ColorDepth>,
+    #[arg(
+        long = "border",
+        default_value_t = crate::rendering::Border::Single,
+        help = "style of the frame drawn around the term output (none, single, double, rounded, heavy)"
+    )]
+    border: crate::rendering::Border,
+    #[arg(
+        long = "padding",
+        default_value_t = 1,
+        help = "number of blank characters between the term output and its frame"
+    )]
+    padding: usize,
+    #[arg(
+        long = "no-background",
+        help = "do not paint the background of the term output"
+    )]
+    no_background: bool,
+    #[arg(
+        long = "netpbm-encoding",
+        default_value_t = crate::rendering::NetpbmEncoding::Binary,
+        help = "encoding of the pbm and ppm outputs (plain, binary)"
+    )]
+    netpbm_encoding: crate::rendering::NetpbmEncoding,
+    #[arg(
+        long = "jpeg-quality",
+        default_value_t = 90,
+        value_parser = clap::value_parser!(u8).range(1..=100),
+        help = "quality of the jpeg output, from 1 to 100"
+    )]
+    jpeg_quality: u8,
+    #[arg(
+        long = "stl-encoding",
+        default_value_t = crate::rendering::StlEncoding::Binary,
+        help = "encoding of the stl output (ascii, binary)"
+    )]
+    stl_encoding: crate::rendering::StlEncoding,
+    #[arg(
+        long = "mask",
+        default_value_t = CliMask::Square,
+        help = "shape of the png and svg outputs, outside of which the image is transparent (square, circle, rounded, squircle)"
+    )]
+    mask: CliMask,
+    #[arg(
+        long = "mask-radius",
+        default_value_t = 0.25,
+        value_parser = parse_fraction,
+        help = "radius of the corners of the rounded mask, as a fraction of half the image size between 0 and 1"
+    )]
+    mask_radius: f32,
+    #[arg(
+        long = "cell-shape",
+        default_value_t = crate::rendering::CellShape::Square,
+        help = "shape of the filled cells of the png and svg outputs (square, dot, diamond, rounded, triangle)"
+    )]
+    cell_shape: crate::rendering::CellShape,
+    #[arg(
+        long = "gap",
+        default_value_t = 0,
+        value_parser = clap::value_parser!(u32).range(0..50),
+        help = "space left empty between the cells of the png and svg outputs, in pixels"
+    )]
+    gap: u32,
+    #[arg(
+        long = "grid-lines",
+        help = "draws lines along the border of every cell of the png and svg outputs"
+    )]
+    grid_lines: bool,
+    #[arg(
+        long = "grid-color",
+        default_value = "#e0e0e0",
+        help = "color of the grid lines, as #rrggbb"
+    )]
+    grid_color: crate::rendering::HexColor,
+    #[arg(
+        long = "px",
+        value_parser = clap::value_parser!(u32).range(1..=8192),
+        help = "width and height of the png output, in pixels (defaults to 50 pixels per cell)"
+    )]
+    px: Option<u32>,
+    #[arg(
+        long = "model-cell-size",
+        default_value_t = 5.0,
+        value_parser = parse_positive,
+        help = "width of a cell of the stl and obj outputs"
+    )]
+    model_cell_size: f32,
+    #[arg(
+        long = "model-height",
+        default_value_t = 2.0,
+        value_parser = parse_positive,
+        help = "height of the filled cells of the stl and obj outputs, above the base plate"
+    )]
+    model_height: f32,
+    #[arg(
+        long = "model-base",
+        default_value_t = 1.0,
+        value_parser = parse_non_negative,
+        help = "thickness of the base plate of the stl and obj outputs, 0 to remove it"
+    )]
+    model_base: f32,
+    #[arg(
+        long = "normalize",
+        value_delimiter = ',',
+        help = "comma separated normalizations applied in order to the word before it is hashed (trim, lowercase, nfc, nfkc, casefold, email, uuid)"
+    )]
+    normalize: Vec<crate::normalization::Normalization>,
+    #[command(flatten)]
+    hashing: CliHashing,
+}
+
+#[derive(clap::Parser)]
+#[command(
+    version,
+    about,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+    #[command(flatten)]
+    options: CliOptions,
     #[arg(
         short = 'f',
         long = "file",
         default_value = "-",
-        help = "file where the image should be written. '-' is used to mean stdout."
+        help = "file where the image should be written, or directory for the favicon output. '-' is used to mean stdout."
     )]
     filename: String,
 
-    #[arg(help = "word used as a base value to generate the image")]
-    word: String,
+    #[arg(
+        required = true,
+        help = "word used as a base value to generate the image"
+    )]
+    word: Option<String>,
+}
+
+#[derive(clap::Subcommand)]
+enum CliCommand {
+    /// Generate an image for every word read from a file or stdin
+    Batch(Box<CliBatch>),
+    /// Serve images over HTTP, at /<word>.png, /<word>.svg and /avatar/<hex digest>
+    Serve(CliServe),
+}
+
+#[derive(clap::Args)]
+struct CliServe {
+    #[arg(
+        long = "host",
+        default_value = "127.0.0.1",
+        help = "address the server listens on"
+    )]
+    host: String,
+    #[arg(
+        short = 'p',
+        long = "port",
+        default_value_t = 8080,
+        help = "port the server listens on"
+    )]
+    port: u16,
+    #[arg(
+        long = "normalize",
+        value_delimiter = ',',
+        help = "comma separated normalizations applied in order to the words before they are hashed (trim, lowercase, nfc, nfkc, casefold, email, uuid)"
+    )]
+    normalize: Vec<crate::normalization::Normalization>,
+    #[command(flatten)]
+    hashing: CliHashing,
+}
+
+impl CliServe {
+    fn execute(&self) -> Result<(), crate::error::Error> {
+        let config = crate::server::Config {
+            hash: self.hashing.hash,
+            key: self.hashing.secret().map(<[u8]>::to_vec),
+            normalize: self.normalize.clone(),
+        };
+        let server = crate::server::Server::bind((self.host.as_str(), self.port), config)
+            .map_err(|e| e.context(format!("cannot listen on {}:{}", self.host, self.port)))?;
+        eprintln!("listening on http://{}", server.local_addr()?);
+
+        server.serve()
+    }
+}
+
+#[derive(clap::Args)]
+struct CliBatch {
+    #[arg(
+        short = 'i',
+        long = "input",
+        default_value = "-",
+        help = "file from which the words are read. '-' is used to mean stdin."
+    )]
+    input: String,
+    #[arg(
+        long = "format",
+        default_value_t = crate::batch::InputFormat::Lines,
+        help = "format of the input (lines=one word per line, csv=csv with a header row, jsonl=one json document per line)"
+    )]
+    format: crate::batch::InputFormat,
+    #[arg(
+        long = "column",
+        help = "header of the csv column, or field of the jsonl documents, holding the words"
+    )]
+    column: Option<String>,
+    #[arg(
+        long = "out",
+        default_value = "{slug}.{ext}",
+        help = "path of the file generated for each word, where {word}, {slug}, {hash} and {ext} are replaced by the word, its slug, the hash of its seed and the extension of the output"
+    )]
+    out: crate::batch::Template,
+    #[arg(
+        short = 'j',
+        long = "jobs",
+        help = "number of images generated in parallel, defaults to the number of cpus"
+    )]
+    jobs: Option<std::num::NonZeroUsize>,
+    #[arg(
+        long = "manifest",
+        help = "file where the list of generated images is written, or its path inside the archive. '-' is used to mean stdout."
+    )]
+    manifest: Option<String>,
+    #[arg(
+        long = "manifest-format",
+        default_value_t = crate::batch::ManifestFormat::Csv,
+        help = "format of the manifest (csv, json)"
+    )]
+    manifest_format: crate::batch::ManifestFormat,
+    #[arg(
+        long = "collision-distance",
+        default_value_t = 2,
+        help = "maximum number of different cells for two images of the same color to be reported as colliding"
+    )]
+    collision_distance: usize,
+    #[arg(
+        long = "archive",
+        help = "archive where the images and the manifest are written instead of files, the paths of --out being used inside the archive. '-' is used to mean stdout."
+    )]
+    archive: Option<String>,
+    #[arg(
+        long = "archive-format",
+        default_value_t = crate::archive::ArchiveFormat::Tar,
+        help = "format of the archive (tar, zip)"
+    )]
+    archive_format: crate::archive::ArchiveFormat,
+    #[arg(
+        long = "cache-dir",
+        help = "directory where generated images are kept, so that images whose word and options did not change are not generated again"
+    )]
+    cache_dir: Option<String>,
+    #[command(flatten)]
+    options: CliOptions,
+}
+
+impl CliOutput {
+    /// Extension of the files generated with this format.
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Terminal | Self::Emoji | Self::CrossStitch | Self::Beads => "txt",
+            Self::Jpeg => "jpg",
+            Self::Favicon => "favicon",
+            Self::Html => "html",
+            Self::Css => "css",
+            Self::Json => "json",
+            Self::Pbm => "pbm",
+            Self::Ppm => "ppm",
+            Self::Xpm => "xpm",
+            Self::Svg => "svg",
+            Self::Ico => "ico",
+            Self::Pdf => "pdf",
+            Self::Eps => "eps",
+            Self::Stl => "stl",
+            Self::Obj => "obj",
+            Self::Png => "png",
+            Self::WebP => "webp",
+            Self::Bmp => "bmp",
+            Self::Gif => "gif",
+            Self::Tiff => "tiff",
+        }
+    }
+}
+
+impl CliOptions {
+    fn extrusion(&self) -> crate::mesh::Extrusion {
+        crate::mesh::Extrusion {
+            cell_size: self.model_cell_size,
+            height: self.model_height,
+            base: self.model_base,
+        }
+    }
+
+    fn mask(&self) -> crate::rendering::Mask {
+        match self.mask {
+            CliMask::Square => crate::rendering::Mask::Square,
+            CliMask::Circle => crate::rendering::Mask::Circle,
+            CliMask::Rounded => crate::rendering::Mask::Rounded {
+                radius: self.mask_radius,
+            },
+            CliMask::Squircle => crate::rendering::Mask::Squircle,
+        }
+    }
+
+    fn grid_lines(&self) -> Option<crate::rendering::HexColor> {
+        self.grid_lines.then_some(self.grid_color)
+    }
+
+    fn render_raster<W: std::io::Write + std::io::Seek>(
+        &self,
+        target: &str,
+        w: W,
+        canva: crate::generator::Canva,
+        format: crate::rendering::RasterFormat,
+    ) -> Result<(), crate::error::Error> {
+        crate::rendering::Raster { format }
+            .render(w, canva)
+            .map_err(|e| {
+                e.context(format!(
+                    "cannot generate {} to \"{}\"",
+                    self.output.to_string().to_uppercase(),
+                    target
+                ))
+            })
+    }
+
+    // description of the options the generated images depend on, used to key the cache. It lists
+    // the options of the output explicitly so that options which cannot change the image, or
+    // changes to the fields of this struct, do not invalidate the cache.
+    fn fingerprint(&self) -> String {
+        let mut options = vec![
+            format!("fingerprint {}", FINGERPRINT_VERSION),
+            format!("output {}", self.output),
+            format!("size {}", self.size),
+        ];
+
+        let shape = |options: &mut Vec<String>| {
+            options.push(format!("mask {}", self.mask));
+            if self.mask == CliMask::Rounded {
+                options.push(format!("mask-radius {}", self.mask_radius));
+            }
+            options.push(format!("cell-shape {}", self.cell_shape));
+            options.push(format!("gap {}", self.gap));
+            if let Some(color) = self.grid_lines() {
+                options.push(format!("grid-lines {}", color));
+            }
+        };
+        let extrusion = |options: &mut Vec<String>| {
+            options.push(format!("model-cell-size {}", self.model_cell_size));
+            options.push(format!("model-height {}", self.model_height));
+            options.push(format!("model-base {}", self.model_base));
+        };
+
+        match self.output {
+            CliOutput::Terminal => {
+                // the color depth of the term output may come from the environment
+                let color_depth = self
+                    .color_depth
+                    .unwrap_or_else(crate::rendering::ColorDepth::from_env);
+                options.push(format!("color-depth {}", color_depth));
+                options.push(format!("border {}", self.border));
+                options.push(format!("padding {}", self.padding));
+                options.push(format!("background {}", !self.no_background));
+            }
+            CliOutput::Pbm | CliOutput::Ppm => {
+                options.push(format!("netpbm-encoding {}", self.netpbm_encoding))
+            }
+            CliOutput::Jpeg => options.push(format!("jpeg-quality {}", self.jpeg_quality)),
+            CliOutput::Stl => {
+                options.push(format!("stl-encoding {}", self.stl_encoding));
+                extrusion(&mut options);
+            }
+            CliOutput::Obj => extrusion(&mut options),
+            CliOutput::Svg => shape(&mut options),
+            CliOutput::Png => {
+                shape(&mut options);
+                if let Some(px) = self.px {
+                    options.push(format!("px {}", px));
+                }
+            }
+            _ => {}
+        }
+
+        options.join("\n")
+    }
+
+    // word from which the seed is computed
+    fn normalize(&self, word: &str) -> Result<String, crate::error::Error> {
+        crate::normalization::normalize(word, &self.normalize)
+            .map_err(|e| e.context(format!("cannot normalize \"{}\"", word)))
+    }
+
+    fn render_favicon(&self, word: &str, directory: &str) -> Result<(), crate::error::Error> {
+        let seed = self.hashing.seed(word);
+        let canva = crate::generator::Canva::new(self.size, seed);
+
+        crate::rendering::Favicon {
+            name: word.to_string(),
+        }
+        .render(directory, canva)
+        .map_err(|e| e.context(format!("cannot generate favicon to \"{}\"", directory)))
+    }
+
+    /// Writes the image of the word, `target` being the name of the destination used in error
+    /// messages. The favicon output is not supported as it is written to a directory.
+    fn render<W: std::io::Write + std::io::Seek>(
+        &self,
+        w: W,
+        word: &str,
+        target: &str,
+    ) -> Result<(), crate::error::Error> {
+        let seed = self.hashing.seed(word);
+        let hash = seed.hash();
+        let canva = crate::generator::Canva::new(self.size, seed);
+
+        match self.output {
+            CliOutput::Terminal => {
+                let color_depth = self
+                    .color_depth
+                    .unwrap_or_else(crate::rendering::ColorDepth::from_env);
+                crate::rendering::Terminal {
+                    color_depth,
+                    border: self.border,
+                    padding: self.padding,
+                    background: !self.no_background,
+                }
+                .render(w, canva)
+            }
+            CliOutput::Emoji => crate::rendering::Emoji.render(w, canva)?,
+            CliOutput::Html => crate::rendering::Html::default().render(w, canva)?,
+            CliOutput::Css => crate::rendering::Css::default().render(w, canva)?,
+            CliOutput::Json => crate::rendering::Json { hash }.render(w, canva)?,
+            CliOutput::Pbm => crate::rendering::Pbm {
+                encoding: self.netpbm_encoding,
+            }
+            .render(w, canva)?,
+            CliOutput::Ppm => crate::rendering::Ppm {
+                encoding: self.netpbm_encoding,
+            }
+            .render(w, canva)?,
+            CliOutput::Xpm => crate::rendering::Xpm.render(w, canva)?,
+            CliOutput::Svg => crate::rendering::Svg {
+                mask: self.mask(),
+                cell_shape: self.cell_shape,
+                gap: self.gap,
+                grid_lines: self.grid_lines(),
+            }
+            .render(w, canva)?,
+            CliOutput::Ico => crate::rendering::Ico::default().render(w, canva)?,
+            CliOutput::Favicon => unreachable!("favicon is written to a directory"),
+            CliOutput::Pdf => crate::rendering::Pdf.render(w, canva)?,
+            CliOutput::Eps => crate::rendering::Eps.render(w, canva)?,
+            CliOutput::Stl => crate::rendering::Stl {
+                encoding: self.stl_encoding,
+                extrusion: self.extrusion(),
+            }
+            .render(w, canva)?,
+            CliOutput::Obj => crate::rendering::Obj {
+                extrusion: self.extrusion(),
+            }
+            .render(w, canva)?,
+            CliOutput::CrossStitch => crate::rendering::Chart {
+                kind: crate::rendering::ChartKind::CrossStitch,
+            }
+            .render(w, canva)?,
+            CliOutput::Beads => crate::rendering::Chart {
+                kind: crate::rendering::ChartKind::Beads,
+            }
+            .render(w, canva)?,
+            CliOutput::Png => {
+                crate::rendering::Png {
+                    mask: self.mask(),
+                    cell_shape: self.cell_shape,
+                    gap: self.gap,
+                    grid_lines: self.grid_lines(),
+                    image_size: self.px,
+                }
+                .render(w, canva)
+                .map_err(|e| e.context(format!("cannot generate PNG to \"{}\"", target)))?;
+            }
+            CliOutput::Jpeg => self.render_raster(
+                target,
+                w,
+                canva,
+                crate::rendering::RasterFormat::Jpeg {
+                    quality: self.jpeg_quality,
+                },
+            )?,
+            CliOutput::WebP => {
+                self.render_raster(target, w, canva, crate::rendering::RasterFormat::WebP)?
+            }
+            CliOutput::Bmp => {
+                self.render_raster(target, w, canva, crate::rendering::RasterFormat::Bmp)?
+            }
+            CliOutput::Gif => {
+                self.render_raster(target, w, canva, crate::rendering::RasterFormat::Gif)?
+            }
+            CliOutput::Tiff => {
+                self.render_raster(target, w, canva, crate::rendering::RasterFormat::Tiff)?
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Cli {
@@ -83,72 +785,740 @@ impl Cli {
     }
 
     pub fn execute(&self) -> Result<(), crate::error::Error> {
-        let seed: crate::generator::Seed = self.word.as_str().into();
-        let canva = crate::generator::Canva::new(self.size, seed);
-        let file = self.file()?;
+        match &self.command {
+            Some(CliCommand::Batch(batch)) => return batch.execute(),
+            Some(CliCommand::Serve(serve)) => return serve.execute(),
+            None => {}
+        }
 
-        match self.output {
-            CliOutput::Terminal => crate::rendering::Terminal.render(file, canva),
-            CliOutput::Png => {
-                crate::rendering::Png.render(file, canva).map_err(|e| {
-                    e.context(format!("cannot generate PNG to \"{}\"", self.filename))
-                })?;
+        let word = self
+            .options
+            .normalize(self.word.as_deref().unwrap_or_default())?;
+        let word = word.as_str();
+
+        if self.options.output == CliOutput::Favicon {
+            if self.filename == "-" {
+                return Err(crate::error::Error::generic(
+                    "favicon output requires a directory to be given with --file".to_string(),
+                ));
             }
+
+            return self.options.render_favicon(word, &self.filename);
         }
 
-        Ok(())
+        let file = self.file()?;
+        self.options.render(file, word, &self.filename)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
+// how the image of a word of a batch was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Status {
+    Generated,
+    Cached,
+    Unchanged,
+}
 
-    #[test]
-    fn cli_output_from_string_term() {
-        let out = super::CliOutput::from_str("term").expect("failed to build a valid CLI output");
+impl CliBatch {
+    fn words(&self) -> Result<Vec<String>, crate::error::Error> {
+        let column = self.column.as_deref();
 
-        assert_eq!(super::CliOutput::Terminal, out)
+        if self.input == "-" {
+            crate::batch::read_words(std::io::stdin().lock(), self.format, column)
+                .map_err(|e| e.context("cannot read words from stdin".to_string()))
+        } else {
+            std::fs::File::open(&self.input)
+                .map_err(crate::error::Error::from)
+                .and_then(|file| crate::batch::read_words(file, self.format, column))
+                .map_err(|e| e.context(format!("cannot read words from \"{}\"", self.input)))
+        }
     }
 
-    #[test]
-    fn cli_output_from_string_png() {
-        let out = super::CliOutput::from_str("png").expect("failed to build a valid CLI output");
+    fn entry(&self, word: &str) -> crate::batch::Entry {
+        let seed = self.options.hashing.seed(word);
+        let hash = seed.hash();
+        let canva = crate::generator::Canva::new(self.options.size, seed);
 
-        assert_eq!(super::CliOutput::Png, out)
+        crate::batch::Entry {
+            word: word.to_string(),
+            path: self
+                .out
+                .expand(word, &hash, self.options.output.extension()),
+            hash,
+            color: canva.color(),
+            cells: canva.into_iter().map(|(_, shown)| shown).collect(),
+            error: None,
+        }
     }
 
-    #[test]
-    fn cli_output_from_string_unexpected() {
-        let err = super::CliOutput::from_str("nope").err();
+    fn write_manifest(
+        &self,
+        entries: &[crate::batch::Entry],
+        collisions: &[crate::batch::Collision],
+    ) -> Result<(), crate::error::Error> {
+        let Some(manifest) = &self.manifest else {
+            return Ok(());
+        };
 
-        assert_eq!(Some("unsupported output format 'nope'".to_string()), err)
-    }
+        if manifest == "-" {
+            return crate::batch::write_manifest(
+                std::io::stdout().lock(),
+                self.manifest_format,
+                entries,
+                collisions,
+            );
+        }
 
-    #[test]
-    fn cli_output_display_term() {
-        assert_eq!("term", format!("{}", super::CliOutput::Terminal))
+        std::fs::File::create(manifest)
+            .map_err(crate::error::Error::from)
+            .and_then(|file| {
+                crate::batch::write_manifest(
+                    std::io::BufWriter::new(file),
+                    self.manifest_format,
+                    entries,
+                    collisions,
+                )
+            })
+            .map_err(|e| e.context(format!("cannot write manifest to \"{}\"", manifest)))
     }
 
-    #[test]
-    fn cli_output_display_png() {
-        assert_eq!("png", format!("{}", super::CliOutput::Png))
-    }
+    fn archive(
+        &self,
+    ) -> Result<Option<crate::archive::Archive<Box<dyn std::io::Write>>>, crate::error::Error> {
+        let Some(archive) = &self.archive else {
+            return Ok(None);
+        };
 
-    #[test]
-    fn cli_execute_ascii() {
-        let file = Tempfile::new();
+        if self.options.output == CliOutput::Favicon {
+            return Err(crate::error::Error::generic(
+                "favicon output cannot be written to an archive".to_string(),
+            ));
+        }
 
-        let cli = super::Cli {
-            output: super::CliOutput::Terminal,
-            word: "hello".to_string(),
-            size: 5,
-            filename: file.name.to_string(),
+        let w: Box<dyn std::io::Write> = if archive == "-" {
+            Box::new(std::io::stdout().lock())
+        } else {
+            let file = std::fs::File::create(archive).map_err(|e| {
+                crate::error::Error::from(e).context(format!("cannot open \"{}\"", archive))
+            })?;
+            Box::new(std::io::BufWriter::new(file))
         };
 
-        cli.execute().expect("failed to generate image");
+        Ok(Some(crate::archive::Archive::new(w, self.archive_format)))
+    }
 
-        let expect = include_str!("../testdata/terminal_render.ascii");
+    fn archive_error(&self, err: crate::error::Error) -> crate::error::Error {
+        let archive = self.archive.as_deref().unwrap_or_default();
+
+        err.context(format!("cannot write archive to \"{}\"", archive))
+    }
+
+    fn cache(&self) -> Result<Option<crate::batch::Cache>, crate::error::Error> {
+        let Some(directory) = &self.cache_dir else {
+            return Ok(None);
+        };
+
+        if self.options.output == CliOutput::Favicon {
+            return Err(crate::error::Error::generic(
+                "favicon output cannot be cached".to_string(),
+            ));
+        }
+
+        Ok(Some(crate::batch::Cache::new(
+            directory,
+            self.options.fingerprint(),
+        )))
+    }
+
+    // image of the word, taken from the cache when it holds it
+    fn content(
+        &self,
+        entry: &crate::batch::Entry,
+        cache: Option<&crate::batch::Cache>,
+    ) -> Result<(Status, Vec<u8>), crate::error::Error> {
+        let fingerprint = cache.map(|cache| cache.fingerprint(&entry.hash));
+        if let (Some(cache), Some(fingerprint)) = (cache, &fingerprint) {
+            if let Some(content) = cache.get(fingerprint)? {
+                return Ok((Status::Cached, content));
+            }
+        }
+
+        let mut content = std::io::Cursor::new(Vec::new());
+        self.options
+            .render(&mut content, &entry.word, &entry.path)?;
+        let content = content.into_inner();
+
+        if let (Some(cache), Some(fingerprint)) = (cache, &fingerprint) {
+            cache.put(fingerprint, &content)?;
+        }
+
+        Ok((Status::Generated, content))
+    }
+
+    // writes the image of the word to its path, or returns its content when it goes to an archive
+    fn generate(
+        &self,
+        entry: &crate::batch::Entry,
+        cache: Option<&crate::batch::Cache>,
+    ) -> Result<(Status, Option<Vec<u8>>), crate::error::Error> {
+        let path = entry.path.as_str();
+
+        if self.archive.is_some() {
+            let (status, content) = self.content(entry, cache)?;
+            return Ok((status, Some(content)));
+        }
+
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                crate::error::Error::from(e)
+                    .context(format!("cannot create \"{}\"", parent.display()))
+            })?;
+        }
+
+        if self.options.output == CliOutput::Favicon {
+            self.options.render_favicon(&entry.word, path)?;
+            return Ok((Status::Generated, None));
+        }
+
+        let open = || {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(true)
+                .write(true)
+                .open(path)
+                .map_err(|e| {
+                    crate::error::Error::from(e).context(format!("cannot open \"{}\"", path))
+                })
+        };
+
+        if cache.is_none() {
+            self.options.render(open()?, &entry.word, path)?;
+            return Ok((Status::Generated, None));
+        }
+
+        let (status, content) = self.content(entry, cache)?;
+        if status == Status::Cached && std::fs::read(path).is_ok_and(|current| current == content) {
+            return Ok((Status::Unchanged, None));
+        }
+
+        std::io::Write::write_all(&mut open()?, &content).map_err(|e| {
+            crate::error::Error::from(e).context(format!("cannot write \"{}\"", path))
+        })?;
+
+        Ok((status, None))
+    }
+
+    fn execute(&self) -> Result<(), crate::error::Error> {
+        let mut archive = self.archive()?;
+        let cache = self.cache()?;
+        let words = self.words()?;
+        let jobs = self
+            .jobs
+            .or_else(|| std::thread::available_parallelism().ok())
+            .map_or(1, std::num::NonZeroUsize::get);
+
+        // a path is only written by the first word using it, so that two threads never write the
+        // same file and the output does not depend on the scheduling
+        let mut owners = std::collections::HashMap::new();
+        let mut entries: Vec<crate::batch::Entry> = words
+            .iter()
+            .filter_map(|word| {
+                let word = match self.options.normalize(word) {
+                    Ok(word) if word.is_empty() => return None,
+                    Ok(word) => word,
+                    // the word is kept as given, and reported as a failure like the other errors
+                    Err(err) => {
+                        let mut entry = self.entry(word);
+                        entry.error = Some(err.to_string());
+                        return Some(entry);
+                    }
+                };
+
+                let mut entry = self.entry(&word);
+                match owners.entry(entry.path.clone()) {
+                    std::collections::hash_map::Entry::Occupied(owner) => {
+                        entry.error = Some(format!(
+                            "\"{}\" is already generated for \"{}\"",
+                            entry.path,
+                            owner.get()
+                        ))
+                    }
+                    std::collections::hash_map::Entry::Vacant(owner) => {
+                        owner.insert(word);
+                    }
+                };
+
+                Some(entry)
+            })
+            .collect();
+
+        // words are generated by chunks so that only the images of a chunk are kept in memory
+        // before being written to the archive, in the order of the input
+        let mut failures = 0;
+        let mut statuses = std::collections::HashMap::new();
+        for chunk in entries.chunks_mut(jobs * 64) {
+            let results = crate::batch::parallel_map(chunk, jobs, |entry| match &entry.error {
+                Some(error) => Err(crate::error::Error::generic(error.clone())),
+                None => self.generate(entry, cache.as_ref()),
+            });
+
+            for (entry, result) in chunk.iter_mut().zip(results) {
+                let result = result.and_then(|(status, content)| {
+                    if let (Some(content), Some(archive)) = (content, archive.as_mut()) {
+                        archive
+                            .append(&entry.path, &content)
+                            .map_err(|e| self.archive_error(e))?;
+                    }
+
+                    Ok(status)
+                });
+
+                match result {
+                    Ok(status) => *statuses.entry(status).or_insert(0) += 1,
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        entry.error = Some(err.to_string());
+                        failures += 1;
+                    }
+                }
+            }
+        }
+
+        if cache.is_some() {
+            let count = |status| statuses.get(&status).copied().unwrap_or(0);
+            eprintln!(
+                "{} generated, {} taken from the cache, {} unchanged",
+                count(Status::Generated),
+                count(Status::Cached),
+                count(Status::Unchanged)
+            );
+        }
+
+        // words which failed have no image to be confused with, the indexes of their collisions
+        // are mapped back to all the entries
+        let generated: Vec<usize> = (0..entries.len())
+            .filter(|index| entries[*index].error.is_none())
+            .collect();
+        let images: Vec<crate::batch::Entry> = generated
+            .iter()
+            .map(|index| entries[*index].clone())
+            .collect();
+        let collisions: Vec<crate::batch::Collision> =
+            crate::batch::collisions(&images, self.collision_distance)
+                .into_iter()
+                .map(|collision| crate::batch::Collision {
+                    first: generated[collision.first],
+                    second: generated[collision.second],
+                    ..collision
+                })
+                .collect();
+        collisions.iter().for_each(|collision| {
+            let first = &entries[collision.first].word;
+            let second = &entries[collision.second].word;
+            match collision.distance {
+                0 => eprintln!("\"{}\" and \"{}\" generate identical images", first, second),
+                distance => eprintln!(
+                    "\"{}\" and \"{}\" generate similar images ({} different cells)",
+                    first, second, distance
+                ),
+            }
+        });
+
+        match archive {
+            Some(mut archive) => {
+                let name = self
+                    .manifest
+                    .clone()
+                    .unwrap_or_else(|| format!("manifest.{}", self.manifest_format));
+                let mut manifest = Vec::new();
+                crate::batch::write_manifest(
+                    &mut manifest,
+                    self.manifest_format,
+                    &entries,
+                    &collisions,
+                )?;
+
+                archive
+                    .append(&name, &manifest)
+                    .and_then(|_| archive.finish())
+                    .map_err(|e| self.archive_error(e))?;
+            }
+            None => self.write_manifest(&entries, &collisions)?,
+        }
+
+        if failures > 0 {
+            return Err(crate::error::Error::generic(format!(
+                "{} of {} words failed",
+                failures,
+                entries.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    #[test]
+    fn cli_output_from_string_term() {
+        let out = super::CliOutput::from_str("term").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Terminal, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_emoji() {
+        let out = super::CliOutput::from_str("emoji").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Emoji, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_html() {
+        let out = super::CliOutput::from_str("html").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Html, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_css() {
+        let out = super::CliOutput::from_str("css").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Css, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_json() {
+        let out = super::CliOutput::from_str("json").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Json, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_pbm() {
+        let out = super::CliOutput::from_str("pbm").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Pbm, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_ppm() {
+        let out = super::CliOutput::from_str("ppm").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Ppm, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_xpm() {
+        let out = super::CliOutput::from_str("xpm").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Xpm, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_png() {
+        let out = super::CliOutput::from_str("png").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Png, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_jpeg() {
+        let out = super::CliOutput::from_str("jpeg").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Jpeg, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_webp() {
+        let out = super::CliOutput::from_str("webp").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::WebP, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_bmp() {
+        let out = super::CliOutput::from_str("bmp").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Bmp, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_gif() {
+        let out = super::CliOutput::from_str("gif").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Gif, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_tiff() {
+        let out = super::CliOutput::from_str("tiff").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Tiff, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_svg() {
+        let out = super::CliOutput::from_str("svg").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Svg, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_ico() {
+        let out = super::CliOutput::from_str("ico").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Ico, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_favicon() {
+        let out =
+            super::CliOutput::from_str("favicon").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Favicon, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_pdf() {
+        let out = super::CliOutput::from_str("pdf").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Pdf, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_eps() {
+        let out = super::CliOutput::from_str("eps").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Eps, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_stl() {
+        let out = super::CliOutput::from_str("stl").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Stl, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_obj() {
+        let out = super::CliOutput::from_str("obj").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Obj, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_cross_stitch() {
+        let out =
+            super::CliOutput::from_str("cross-stitch").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::CrossStitch, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_beads() {
+        let out = super::CliOutput::from_str("beads").expect("failed to build a valid CLI output");
+
+        assert_eq!(super::CliOutput::Beads, out)
+    }
+
+    #[test]
+    fn cli_output_from_string_unexpected() {
+        let err = super::CliOutput::from_str("nope").err();
+
+        assert_eq!(Some("unsupported output format 'nope'".to_string()), err)
+    }
+
+    #[test]
+    fn cli_output_display_term() {
+        assert_eq!("term", format!("{}", super::CliOutput::Terminal))
+    }
+
+    #[test]
+    fn cli_output_display_emoji() {
+        assert_eq!("emoji", format!("{}", super::CliOutput::Emoji))
+    }
+
+    #[test]
+    fn cli_output_display_html() {
+        assert_eq!("html", format!("{}", super::CliOutput::Html))
+    }
+
+    #[test]
+    fn cli_output_display_css() {
+        assert_eq!("css", format!("{}", super::CliOutput::Css))
+    }
+
+    #[test]
+    fn cli_output_display_json() {
+        assert_eq!("json", format!("{}", super::CliOutput::Json))
+    }
+
+    #[test]
+    fn cli_output_display_pbm() {
+        assert_eq!("pbm", format!("{}", super::CliOutput::Pbm))
+    }
+
+    #[test]
+    fn cli_output_display_ppm() {
+        assert_eq!("ppm", format!("{}", super::CliOutput::Ppm))
+    }
+
+    #[test]
+    fn cli_output_display_xpm() {
+        assert_eq!("xpm", format!("{}", super::CliOutput::Xpm))
+    }
+
+    #[test]
+    fn cli_output_display_png() {
+        assert_eq!("png", format!("{}", super::CliOutput::Png))
+    }
+
+    #[test]
+    fn cli_output_display_jpeg() {
+        assert_eq!("jpeg", format!("{}", super::CliOutput::Jpeg))
+    }
+
+    #[test]
+    fn cli_output_display_webp() {
+        assert_eq!("webp", format!("{}", super::CliOutput::WebP))
+    }
+
+    #[test]
+    fn cli_output_display_bmp() {
+        assert_eq!("bmp", format!("{}", super::CliOutput::Bmp))
+    }
+
+    #[test]
+    fn cli_output_display_gif() {
+        assert_eq!("gif", format!("{}", super::CliOutput::Gif))
+    }
+
+    #[test]
+    fn cli_output_display_tiff() {
+        assert_eq!("tiff", format!("{}", super::CliOutput::Tiff))
+    }
+
+    #[test]
+    fn cli_output_display_svg() {
+        assert_eq!("svg", format!("{}", super::CliOutput::Svg))
+    }
+
+    #[test]
+    fn cli_output_display_ico() {
+        assert_eq!("ico", format!("{}", super::CliOutput::Ico))
+    }
+
+    #[test]
+    fn cli_output_display_favicon() {
+        assert_eq!("favicon", format!("{}", super::CliOutput::Favicon))
+    }
+
+    #[test]
+    fn cli_output_display_pdf() {
+        assert_eq!("pdf", format!("{}", super::CliOutput::Pdf))
+    }
+
+    #[test]
+    fn cli_output_display_eps() {
+        assert_eq!("eps", format!("{}", super::CliOutput::Eps))
+    }
+
+    #[test]
+    fn cli_output_display_stl() {
+        assert_eq!("stl", format!("{}", super::CliOutput::Stl))
+    }
+
+    #[test]
+    fn cli_output_display_obj() {
+        assert_eq!("obj", format!("{}", super::CliOutput::Obj))
+    }
+
+    #[test]
+    fn cli_output_display_cross_stitch() {
+        assert_eq!("cross-stitch", format!("{}", super::CliOutput::CrossStitch))
+    }
+
+    #[test]
+    fn cli_output_display_beads() {
+        assert_eq!("beads", format!("{}", super::CliOutput::Beads))
+    }
+
+    #[test]
+    fn cli_mask_from_string() {
+        assert_eq!(
+            Ok(super::CliMask::Square),
+            super::CliMask::from_str("square")
+        );
+        assert_eq!(
+            Ok(super::CliMask::Circle),
+            super::CliMask::from_str("circle")
+        );
+        assert_eq!(
+            Ok(super::CliMask::Rounded),
+            super::CliMask::from_str("rounded")
+        );
+        assert_eq!(
+            Ok(super::CliMask::Squircle),
+            super::CliMask::from_str("squircle")
+        );
+        assert_eq!(
+            Err("unsupported mask 'nope'".to_string()),
+            super::CliMask::from_str("nope")
+        );
+    }
+
+    #[test]
+    fn cli_mask_display() {
+        assert_eq!("square", format!("{}", super::CliMask::Square));
+        assert_eq!("circle", format!("{}", super::CliMask::Circle));
+        assert_eq!("rounded", format!("{}", super::CliMask::Rounded));
+        assert_eq!("squircle", format!("{}", super::CliMask::Squircle));
+    }
+
+    #[test]
+    fn cli_execute_ascii() {
+        let file = Tempfile::new();
+
+        let cli = super::Cli {
+            command: None,
+            word: Some("hello".to_string()),
+            filename: file.name.to_string(),
+            options: super::CliOptions {
+                output: super::CliOutput::Terminal,
+                size: 5,
+                color_depth: Some(crate::rendering::ColorDepth::Ansi256),
+                border: crate::rendering::Border::Single,
+                padding: 1,
+                no_background: false,
+                netpbm_encoding: crate::rendering::NetpbmEncoding::Binary,
+                jpeg_quality: 90,
+                stl_encoding: crate::rendering::StlEncoding::Binary,
+                mask: super::CliMask::Square,
+                mask_radius: 0.25,
+                cell_shape: crate::rendering::CellShape::Square,
+                gap: 0,
+                grid_lines: false,
+                grid_color: crate::rendering::HexColor([224, 224, 224]),
+                px: None,
+                model_cell_size: 5.0,
+                model_height: 2.0,
+                model_base: 1.0,
+                normalize: Vec::new(),
+                hashing: super::CliHashing {
+                    hash: crate::generator::HashFunction::Sha256,
+                    key: None,
+                    key_file: None,
+                },
+            },
+        };
+
+        cli.execute().expect("failed to generate image");
+
+        let expect = include_str!("../testdata/terminal_render.ascii");
         let actual = std::fs::read_to_string(&file.name).expect("failed to read generated image");
 
         assert_eq!(expect, actual)
@@ -159,10 +1529,36 @@ mod tests {
         let file = Tempfile::new();
 
         let cli = super::Cli {
-            output: super::CliOutput::Png,
-            word: "hello".to_string(),
-            size: 5,
+            command: None,
+            word: Some("hello".to_string()),
             filename: file.name.to_string(),
+            options: super::CliOptions {
+                output: super::CliOutput::Png,
+                size: 5,
+                color_depth: Some(crate::rendering::ColorDepth::Ansi256),
+                border: crate::rendering::Border::Single,
+                padding: 1,
+                no_background: false,
+                netpbm_encoding: crate::rendering::NetpbmEncoding::Binary,
+                jpeg_quality: 90,
+                stl_encoding: crate::rendering::StlEncoding::Binary,
+                mask: super::CliMask::Square,
+                mask_radius: 0.25,
+                cell_shape: crate::rendering::CellShape::Square,
+                gap: 0,
+                grid_lines: false,
+                grid_color: crate::rendering::HexColor([224, 224, 224]),
+                px: None,
+                model_cell_size: 5.0,
+                model_height: 2.0,
+                model_base: 1.0,
+                normalize: Vec::new(),
+                hashing: super::CliHashing {
+                    hash: crate::generator::HashFunction::Sha256,
+                    key: None,
+                    key_file: None,
+                },
+            },
         };
 
         cli.execute().expect("failed to generate image");
@@ -174,6 +1570,676 @@ mod tests {
         assert_eq!(expect, actual)
     }
 
+    #[test]
+    fn cli_output_extension() {
+        assert_eq!("txt", super::CliOutput::Terminal.extension());
+        assert_eq!("png", super::CliOutput::Png.extension());
+        assert_eq!("jpg", super::CliOutput::Jpeg.extension());
+        assert_eq!("svg", super::CliOutput::Svg.extension());
+    }
+
+    #[test]
+    fn cli_parse_requires_word() {
+        use clap::Parser;
+
+        assert!(super::Cli::try_parse_from(["pixie"]).is_err());
+        assert!(super::Cli::try_parse_from(["pixie", "hello"]).is_ok());
+        assert!(super::Cli::try_parse_from(["pixie", "batch"]).is_ok());
+    }
+
+    #[test]
+    fn cli_options_fingerprint() {
+        use clap::Parser;
+
+        let fingerprint = |args: &[&str]| {
+            let cli = super::Cli::try_parse_from(["pixie"].iter().chain(args).chain(&["hello"]))
+                .expect("failed to parse arguments");
+            cli.options.fingerprint()
+        };
+
+        let png = fingerprint(&["-o", "png"]);
+        assert_eq!(
+            "fingerprint 1\noutput png\nsize 10\nmask square\ncell-shape square\ngap 0",
+            png
+        );
+
+        // options of other outputs do not change the images
+        assert_eq!(png, fingerprint(&["-o", "png", "--jpeg-quality", "10"]));
+        assert_eq!(png, fingerprint(&["-o", "png", "--mask-radius", "0.5"]));
+        assert_eq!(png, fingerprint(&["-o", "png", "--hash", "sha512"]));
+
+        assert_ne!(png, fingerprint(&["-o", "png", "--gap", "1"]));
+        assert_ne!(png, fingerprint(&["-o", "png", "--px", "64"]));
+        assert_ne!(png, fingerprint(&["-o", "png", "--size", "6"]));
+        assert_ne!(png, fingerprint(&["-o", "svg"]));
+        assert_ne!(
+            fingerprint(&["-o", "png", "--mask", "rounded"]),
+            fingerprint(&["-o", "png", "--mask", "rounded", "--mask-radius", "0.5"])
+        );
+        assert_ne!(
+            fingerprint(&["-o", "jpeg"]),
+            fingerprint(&["-o", "jpeg", "--jpeg-quality", "10"])
+        );
+    }
+
+    #[test]
+    fn cli_parse_mask_radius() {
+        use clap::Parser;
+
+        let parse = |radius: &str| {
+            super::Cli::try_parse_from([
+                "pixie",
+                "--mask",
+                "rounded",
+                "--mask-radius",
+                radius,
+                "hello",
+            ])
+        };
+
+        for radius in ["0", "0.5", "1"] {
+            let cli = parse(radius).expect("failed to parse arguments");
+            assert_eq!(radius.parse::<f32>().ok(), Some(cli.options.mask_radius));
+        }
+        for radius in ["5", "-1", "1.01", "NaN", "half"] {
+            assert!(parse(radius).is_err(), "{}", radius);
+        }
+    }
+
+    #[test]
+    fn cli_parse_model() {
+        use clap::Parser;
+
+        let parse = |args: &[&str]| {
+            super::Cli::try_parse_from(["pixie", "-o", "stl"].iter().chain(args).chain(&["hello"]))
+        };
+
+        let cli = parse(&["--model-cell-size", "2.5", "--model-base", "0"])
+            .expect("failed to parse arguments");
+        assert_eq!(2.5, cli.options.model_cell_size);
+        assert_eq!(0.0, cli.options.model_base);
+
+        for args in [
+            ["--model-cell-size", "0"],
+            ["--model-cell-size=-5", "--model-height=2"],
+            ["--model-height", "0"],
+            ["--model-height", "inf"],
+            ["--model-height", "NaN"],
+            ["--model-base=-1", "--model-height=2"],
+            ["--model-base", "one"],
+        ] {
+            assert!(parse(&args).is_err(), "{:?}", args);
+        }
+    }
+
+    #[test]
+    fn cli_execute_hash() {
+        use clap::Parser;
+
+        let file = Tempfile::new();
+
+        let cli = super::Cli::try_parse_from([
+            "pixie", "--hash", "md5", "-o", "json", "-f", &file.name, "hello",
+        ])
+        .expect("failed to parse arguments");
+        cli.execute().expect("failed to generate image");
+
+        let actual = std::fs::read_to_string(&file.name).expect("failed to read generated image");
+        assert!(actual.contains("5d41402abc4b2a76b9719d911017c592"));
+        assert!(super::Cli::try_parse_from(["pixie", "--hash", "sha1", "hello"]).is_err());
+    }
+
+    #[test]
+    fn cli_execute_key() {
+        use clap::Parser;
+
+        let file = Tempfile::new();
+        let key_file = Tempfile::new();
+        std::fs::write(&key_file.name, "secret\r\n").expect("failed to write key");
+
+        let canva =
+            crate::generator::Canva::new(5, crate::generator::Seed::keyed("hello", b"secret"));
+        let mut expect = Vec::new();
+        crate::rendering::Svg::default()
+            .render(&mut expect, canva)
+            .expect("failed to render SVG");
+
+        for key in [["--key", "secret"], ["--key-file", &key_file.name]] {
+            let cli = super::Cli::try_parse_from(
+                ["pixie", "-s", "5", "-o", "svg", "-f", &file.name, "hello"]
+                    .into_iter()
+                    .chain(key),
+            )
+            .expect("failed to parse arguments");
+            cli.execute().expect("failed to generate image");
+
+            let actual = std::fs::read(&file.name).expect("failed to read generated image");
+            assert_eq!(expect, actual);
+        }
+    }
+
+    #[test]
+    fn cli_key() {
+        use clap::Parser;
+
+        let key_file = Tempfile::new();
+        std::fs::write(&key_file.name, "file secret\n").expect("failed to write key");
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "--key",
+            "secret",
+            "--key-file",
+            &key_file.name,
+            "hello",
+        ])
+        .expect("failed to parse arguments");
+        assert_eq!(Some(&b"file secret"[..]), cli.options.hashing.secret());
+        assert!(!format!("{:?}", cli.options).contains("secret"));
+
+        std::fs::write(&key_file.name, "\n").expect("failed to write key");
+        assert!(
+            super::Cli::try_parse_from(["pixie", "--key-file", &key_file.name, "hello"]).is_err()
+        );
+        assert!(super::Cli::try_parse_from(["pixie", "--key", "", "hello"]).is_err());
+        assert!(
+            super::Cli::try_parse_from(["pixie", "--key-file", "/nonexistent", "hello"]).is_err()
+        );
+    }
+
+    #[test]
+    fn cli_parse_serve() {
+        use clap::Parser;
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "serve",
+            "--port",
+            "9000",
+            "--key",
+            "secret",
+            "--normalize",
+            "trim,email",
+        ])
+        .expect("failed to parse arguments");
+
+        let Some(super::CliCommand::Serve(serve)) = cli.command else {
+            panic!("expected serve command");
+        };
+        assert_eq!(9000, serve.port);
+        assert_eq!("127.0.0.1", serve.host);
+        assert_eq!(Some(&b"secret"[..]), serve.hashing.secret());
+        assert_eq!(
+            vec![
+                crate::normalization::Normalization::Trim,
+                crate::normalization::Normalization::Email
+            ],
+            serve.normalize
+        );
+    }
+
+    #[test]
+    fn cli_execute_batch() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.csv");
+        std::fs::write(&input, "id,name\n1,hello\n2,Hello World\n").expect("failed to write words");
+        let out = format!("{}/avatars/{{slug}}.{{ext}}", directory.display());
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--format",
+            "csv",
+            "--column",
+            "name",
+            "--out",
+            &out,
+            "-o",
+            "png",
+            "-s",
+            "5",
+        ])
+        .expect("failed to parse arguments");
+        cli.execute().expect("failed to generate images");
+
+        let expect =
+            std::fs::read("testdata/png_render.png").expect("failed to read expected image");
+        let hello = std::fs::read(directory.join("avatars/hello.png"))
+            .expect("failed to read generated image");
+        assert_eq!(expect, hello);
+        assert!(directory.join("avatars/hello-world.png").exists());
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_normalize() {
+        use clap::Parser;
+
+        let file = Tempfile::new();
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "--normalize",
+            "trim,lowercase",
+            "-s",
+            "5",
+            "-o",
+            "png",
+            "-f",
+            &file.name,
+            " HELLO\t",
+        ])
+        .expect("failed to parse arguments");
+        assert_eq!(
+            vec![
+                crate::normalization::Normalization::Trim,
+                crate::normalization::Normalization::Lowercase
+            ],
+            cli.options.normalize
+        );
+        cli.execute().expect("failed to generate image");
+
+        let expect =
+            std::fs::read("testdata/png_render.png").expect("failed to read expected image");
+        let actual = std::fs::read(&file.name).expect("failed to read generated image");
+        assert_eq!(expect, actual);
+
+        let invalid = super::Cli::try_parse_from(["pixie", "--normalize", "email", "hello"])
+            .expect("failed to parse arguments");
+        assert!(invalid.execute().is_err());
+        assert!(super::Cli::try_parse_from(["pixie", "--normalize", "upper", "hello"]).is_err());
+    }
+
+    #[test]
+    fn cli_execute_batch_normalize() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.txt");
+        std::fs::write(&input, "Alice+news@Example.com\nBob@GMAIL.com\n")
+            .expect("failed to write words");
+        let out = format!("{}/{{word}}.{{ext}}", directory.display());
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--out",
+            &out,
+            "--normalize",
+            "email",
+        ])
+        .expect("failed to parse arguments");
+        cli.execute().expect("failed to generate images");
+
+        assert!(directory.join("alice+news@example.com.txt").exists());
+        assert!(directory.join("bob@gmail.com.txt").exists());
+
+        // a word which cannot be normalized does not stop the others
+        std::fs::write(&input, "bob\nCarol@Example.com\n").expect("failed to write words");
+        let manifest = directory.join("manifest.json");
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--out",
+            &out,
+            "--normalize",
+            "email",
+            "--manifest",
+            &manifest.display().to_string(),
+            "--manifest-format",
+            "json",
+        ])
+        .expect("failed to parse arguments");
+        let err = cli.execute().expect_err("invalid email should fail");
+
+        assert_eq!("1 of 2 words failed", err.to_string());
+        assert!(directory.join("carol@example.com.txt").exists());
+
+        let manifest = std::fs::read(&manifest).expect("failed to read manifest");
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&manifest).expect("failed to parse manifest");
+        assert_eq!(serde_json::json!("bob"), manifest[0]["word"]);
+        assert_eq!(
+            serde_json::json!("cannot normalize \"bob\": invalid email 'bob'"),
+            manifest[0]["error"]
+        );
+        assert_eq!(serde_json::Value::Null, manifest[1]["error"]);
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_batch_reports_failures() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.txt");
+        std::fs::write(&input, "hello\nHello\nworld\n").expect("failed to write words");
+        let out = format!("{}/{{slug}}.{{ext}}", directory.display());
+        let manifest = directory.join("manifest.json");
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--out",
+            &out,
+            "--jobs",
+            "2",
+            "-o",
+            "svg",
+            "--manifest",
+            &manifest.display().to_string(),
+            "--manifest-format",
+            "json",
+        ])
+        .expect("failed to parse arguments");
+        let err = cli.execute().expect_err("duplicated path should fail");
+
+        assert_eq!("1 of 3 words failed", err.to_string());
+        assert!(directory.join("hello.svg").exists());
+        assert!(directory.join("world.svg").exists());
+
+        let manifest = std::fs::read(&manifest).expect("failed to read manifest");
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&manifest).expect("failed to parse manifest");
+        let errors: Vec<&serde_json::Value> = manifest
+            .as_array()
+            .expect("manifest should be an array")
+            .iter()
+            .map(|entry| &entry["error"])
+            .collect();
+        assert_eq!(
+            vec![
+                &serde_json::Value::Null,
+                &serde_json::json!(format!(
+                    "\"{}\" is already generated for \"hello\"",
+                    directory.join("hello.svg").display()
+                )),
+                &serde_json::Value::Null,
+            ],
+            errors
+        );
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_batch_collisions_ignore_failures() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.txt");
+        // "b" and "j" generate identical 2x2 images, the second "b" fails as its path is taken
+        std::fs::write(&input, "b\nb\nj\n").expect("failed to write words");
+        let out = format!("{}/{{slug}}.{{ext}}", directory.display());
+        let manifest = directory.join("manifest.json");
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--out",
+            &out,
+            "-s",
+            "2",
+            "--collision-distance",
+            "0",
+            "--manifest",
+            &manifest.display().to_string(),
+            "--manifest-format",
+            "json",
+        ])
+        .expect("failed to parse arguments");
+        cli.execute().expect_err("duplicated path should fail");
+
+        let manifest = std::fs::read(&manifest).expect("failed to read manifest");
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&manifest).expect("failed to parse manifest");
+        let collisions: Vec<&serde_json::Value> = manifest
+            .as_array()
+            .expect("manifest should be an array")
+            .iter()
+            .map(|entry| &entry["collisions"])
+            .collect();
+        assert_eq!(
+            vec![
+                &serde_json::json!([{ "word": "j", "distance": 0 }]),
+                &serde_json::json!([]),
+                &serde_json::json!([{ "word": "b", "distance": 0 }]),
+            ],
+            collisions
+        );
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_batch_archive() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.txt");
+        std::fs::write(&input, "hello\nworld\n").expect("failed to write words");
+        let archive = directory.join("avatars.tar");
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--out",
+            "avatars/{slug}.{ext}",
+            "--archive",
+            &archive.display().to_string(),
+            "-o",
+            "png",
+            "-s",
+            "5",
+        ])
+        .expect("failed to parse arguments");
+        cli.execute().expect("failed to generate images");
+
+        let archive = std::fs::read(&archive).expect("failed to read archive");
+        let mut reader = tar::Archive::new(archive.as_slice());
+        let files: Vec<(String, Vec<u8>)> = reader
+            .entries()
+            .expect("failed to read archive")
+            .map(|entry| {
+                let mut entry = entry.expect("failed to read entry");
+                let path = entry
+                    .path()
+                    .expect("failed to read path")
+                    .display()
+                    .to_string();
+                let mut content = Vec::new();
+                std::io::Read::read_to_end(&mut entry, &mut content)
+                    .expect("failed to read content");
+                (path, content)
+            })
+            .collect();
+
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            vec!["avatars/hello.png", "avatars/world.png", "manifest.csv"],
+            names
+        );
+        let expect =
+            std::fs::read("testdata/png_render.png").expect("failed to read expected image");
+        assert_eq!(expect, files[0].1);
+        assert!(!directory.join("avatars").exists());
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_batch_archive_reports_failures() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.txt");
+        std::fs::write(&input, "hello\n..\nworld\n").expect("failed to write words");
+        let archive = directory.join("avatars.tar");
+
+        let cli = super::Cli::try_parse_from([
+            "pixie",
+            "batch",
+            "--input",
+            &input.display().to_string(),
+            "--out",
+            "{word}/{slug}.{ext}",
+            "--archive",
+            &archive.display().to_string(),
+            "--manifest-format",
+            "json",
+        ])
+        .expect("failed to parse arguments");
+        let err = cli
+            .execute()
+            .expect_err("path outside of the archive should fail");
+        assert_eq!("1 of 3 words failed", err.to_string());
+
+        let archive = std::fs::read(&archive).expect("failed to read archive");
+        let mut reader = tar::Archive::new(archive.as_slice());
+        let mut files = std::collections::HashMap::new();
+        reader
+            .entries()
+            .expect("failed to read archive")
+            .for_each(|entry| {
+                let mut entry = entry.expect("failed to read entry");
+                let path = entry
+                    .path()
+                    .expect("failed to read path")
+                    .display()
+                    .to_string();
+                let mut content = Vec::new();
+                std::io::Read::read_to_end(&mut entry, &mut content)
+                    .expect("failed to read content");
+                files.insert(path, content);
+            });
+
+        assert!(files.contains_key("hello/hello.txt"));
+        assert!(files.contains_key("world/world.txt"));
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&files["manifest.json"]).expect("failed to parse manifest");
+        assert_eq!(serde_json::Value::Null, manifest[0]["error"]);
+        assert!(manifest[1]["error"]
+            .as_str()
+            .expect("missing error")
+            .starts_with("cannot write archive to"));
+        assert_eq!(serde_json::Value::Null, manifest[2]["error"]);
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_batch_archive_favicon() {
+        use clap::Parser;
+
+        let cli = super::Cli::try_parse_from(["pixie", "batch", "--archive", "-", "-o", "favicon"])
+            .expect("failed to parse arguments");
+        let err = cli.execute().expect_err("favicon should not be archived");
+
+        assert_eq!(
+            "favicon output cannot be written to an archive",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn cli_execute_batch_cache() {
+        use clap::Parser;
+
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&directory).expect("failed to create directory");
+        let input = directory.join("words.txt");
+        std::fs::write(&input, "hello\nworld\n").expect("failed to write words");
+        let out = format!("{}/avatars/{{slug}}.{{ext}}", directory.display());
+        let cache = directory.join("cache");
+        let run = |gap: &str| {
+            super::Cli::try_parse_from([
+                "pixie",
+                "batch",
+                "--input",
+                &input.display().to_string(),
+                "--out",
+                &out,
+                "--cache-dir",
+                &cache.display().to_string(),
+                "-o",
+                "png",
+                "-s",
+                "5",
+                "--gap",
+                gap,
+            ])
+            .expect("failed to parse arguments")
+            .execute()
+            .expect("failed to generate images")
+        };
+        let cached = || {
+            std::fs::read_dir(&cache)
+                .expect("failed to list cache")
+                .map(|entry| {
+                    std::fs::read_dir(entry.expect("failed to read entry").path())
+                        .expect("failed to list cache")
+                        .count()
+                })
+                .sum::<usize>()
+        };
+        let hello = directory.join("avatars/hello.png");
+        let expect =
+            std::fs::read("testdata/png_render.png").expect("failed to read expected image");
+
+        run("0");
+        assert_eq!(2, cached());
+        assert_eq!(expect, std::fs::read(&hello).expect("failed to read image"));
+
+        // outputs which do not match the cache anymore are written again
+        std::fs::write(&hello, "broken").expect("failed to write image");
+        run("0");
+        assert_eq!(2, cached());
+        assert_eq!(expect, std::fs::read(&hello).expect("failed to read image"));
+
+        // other options do not reuse the cached images
+        run("4");
+        assert_eq!(4, cached());
+        assert_ne!(expect, std::fs::read(&hello).expect("failed to read image"));
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove directory");
+    }
+
+    #[test]
+    fn cli_execute_batch_invalid_input() {
+        use clap::Parser;
+
+        let cli = super::Cli::try_parse_from(["pixie", "batch", "--input", "does/not/exist.txt"])
+            .expect("failed to parse arguments");
+        let err = cli.execute().expect_err("missing input should fail");
+
+        assert_eq!(
+            "cannot read words from \"does/not/exist.txt\": entity not found",
+            err.to_string()
+        );
+    }
+
     struct Tempfile {
         name: String,
     }
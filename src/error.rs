@@ -13,6 +13,13 @@ pub enum Kind {
 impl std::error::Error for Error {}
 
 impl Error {
+    pub fn generic(msg: String) -> Self {
+        Self {
+            context: None,
+            kind: Kind::Generic(msg),
+        }
+    }
+
     pub fn context(mut self, msg: String) -> Self {
         self.context = Some(msg);
         self
@@ -147,6 +154,17 @@ mod tests {
         )
     }
 
+    #[test]
+    fn error_generic() {
+        let err = super::Error::generic("my error".to_string());
+        let super::Kind::Generic(msg) = &err.kind else {
+            panic!("unexpected kind")
+        };
+
+        assert_eq!("my error", msg);
+        assert_eq!(None, err.context);
+    }
+
     #[test]
     fn error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -1,5 +1,64 @@
 use sha2::Digest;
 
+/// Version of the algorithm turning a seed into a canva. It must be bumped every time a change
+/// makes the same word generate a different image.
+pub const ALGORITHM_VERSION: u32 = 1;
+
+/// Hash function turning a word into a seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HashFunction {
+    #[default]
+    Sha256,
+    Sha512,
+    Blake3,
+    /// Gives the same seeds as the md5 hashes used by Gravatar.
+    Md5,
+}
+
+impl std::fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+            Self::Blake3 => "blake3",
+            Self::Md5 => "md5",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for HashFunction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Self::Sha256),
+            "sha512" => Ok(Self::Sha512),
+            "blake3" => Ok(Self::Blake3),
+            "md5" => Ok(Self::Md5),
+            value => Err(format!("unsupported hash function '{}'", value)),
+        }
+    }
+}
+
+// digest of the value, or its HMAC when a key is given
+fn digest<D>(value: &[u8], key: Option<&[u8]>) -> Vec<u8>
+where
+    D: Digest + hmac::digest::core_api::BlockSizeUser,
+{
+    match key {
+        Some(key) => {
+            let mut mac = <hmac::SimpleHmac<D> as hmac::Mac>::new_from_slice(key)
+                .expect("HMAC accepts keys of any length");
+            hmac::Mac::update(&mut mac, value);
+            hmac::Mac::finalize(mac).into_bytes().to_vec()
+        }
+        None => D::digest(value).to_vec(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Seed {
     data: Vec<u8>,
     position: usize,
@@ -7,14 +66,57 @@ pub struct Seed {
 
 impl<R: AsRef<str>> From<R> for Seed {
     fn from(value: R) -> Self {
-        let mut hasher = sha2::Sha256::new();
-        hasher.update(value.as_ref());
-        let data = hasher.finalize();
+        Self::with_digest(value.as_ref(), HashFunction::Sha256, None)
+    }
+}
 
-        Self {
-            data: data.to_vec(),
-            position: 0,
+impl Seed {
+    /// Hashes the value with the given function. When a key is given, the keyed variant of the
+    /// function is used instead, HMAC or the keyed mode of BLAKE3, so that the image of a value
+    /// cannot be generated, and matched back to the value, without knowing the key.
+    pub fn with_digest<V: AsRef<[u8]>>(value: V, hash: HashFunction, key: Option<&[u8]>) -> Self {
+        let value = value.as_ref();
+        let data = match hash {
+            HashFunction::Sha256 => digest::<sha2::Sha256>(value, key),
+            HashFunction::Sha512 => digest::<sha2::Sha512>(value, key),
+            HashFunction::Md5 => digest::<md5::Md5>(value, key),
+            // the keyed mode of BLAKE3 takes a 32 bytes key, which is derived from keys of any
+            // length
+            HashFunction::Blake3 => match key {
+                Some(key) => {
+                    let key = blake3::derive_key("pixie seed key", key);
+                    blake3::keyed_hash(&key, value).as_bytes().to_vec()
+                }
+                None => blake3::hash(value).as_bytes().to_vec(),
+            },
+        };
+
+        Self { data, position: 0 }
+    }
+
+    /// Hashes the value with HMAC-SHA256 instead of SHA-256.
+    pub fn keyed<V: AsRef<[u8]>>(value: V, key: &[u8]) -> Self {
+        Self::with_digest(value, HashFunction::Sha256, Some(key))
+    }
+
+    /// Uses an existing digest, such as the md5 of an email address, as the seed instead of hashing
+    /// a word. Returns `None` when the digest is empty.
+    pub fn from_digest(digest: Vec<u8>) -> Option<Self> {
+        if digest.is_empty() {
+            return None;
         }
+
+        Some(Self {
+            data: digest,
+            position: 0,
+        })
+    }
+
+    pub fn hash(&self) -> String {
+        self.data
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
     }
 }
 
@@ -29,6 +131,8 @@ impl Iterator for Seed {
         let value = self.data[self.position];
         self.position += 1;
 
+        // u8::is_multiple_of requires Rust 1.87, newer than the crates of this project need
+        #[allow(clippy::manual_is_multiple_of)]
         Some(value % 2 == 0)
     }
 }
@@ -39,7 +143,7 @@ pub struct Point {
     pub y: usize,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Color {
     Black,
     Blue,
@@ -69,6 +173,23 @@ impl Color {
     }
 }
 
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Black => "black",
+            Self::Blue => "blue",
+            Self::Brown => "brown",
+            Self::Green => "green",
+            Self::Pink => "pink",
+            Self::Purple => "purple",
+            Self::Red => "red",
+            Self::Yellow => "yellow",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
 #[derive(Clone)]
 pub struct Canva {
     color: Color,
@@ -294,6 +415,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn seed_hash() {
+        let generator: super::Seed = "hello".into();
+        assert_eq!(
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            generator.hash()
+        );
+    }
+
+    #[test]
+    fn seed_keyed() {
+        let generator = super::Seed::keyed("hello", b"secret");
+        assert_eq!(0, generator.position);
+        assert_eq!(
+            "88aab3ede8d3adf94d26ab90d3bafd4a2083070c3bcce9c014ee04a443847c0b",
+            generator.hash()
+        );
+
+        let other = super::Seed::keyed("hello", b"other secret");
+        assert_ne!(generator.hash(), other.hash());
+        let plain: super::Seed = "hello".into();
+        assert_ne!(generator.hash(), plain.hash());
+    }
+
+    #[test]
+    fn seed_with_digest() {
+        let hash = |hash, key| super::Seed::with_digest("hello", hash, key).hash();
+
+        assert_eq!(
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+            hash(super::HashFunction::Sha256, None)
+        );
+        assert_eq!(
+            "9b71d224bd62f3785d96d46ad3ea3d73319bfbc2890caadae2dff72519673ca72323c3d99ba5c11d7c7acc6e14b8c5da0c4663475c2e5c3adef46f73bcdec043",
+            hash(super::HashFunction::Sha512, None)
+        );
+        assert_eq!(
+            "ea8f163db38682925e4491c5e58d4bb3506ef8c14eb78a86e908c5624a67200f",
+            hash(super::HashFunction::Blake3, None)
+        );
+        assert_eq!(
+            "5d41402abc4b2a76b9719d911017c592",
+            hash(super::HashFunction::Md5, None)
+        );
+
+        assert_eq!(
+            super::Seed::keyed("hello", b"secret").hash(),
+            hash(super::HashFunction::Sha256, Some(b"secret"))
+        );
+        assert_eq!(
+            "bade63863c61ed0b3165806ecd6acefc",
+            hash(super::HashFunction::Md5, Some(b"secret"))
+        );
+        assert_ne!(
+            hash(super::HashFunction::Blake3, None),
+            hash(super::HashFunction::Blake3, Some(b"secret"))
+        );
+        assert_ne!(
+            hash(super::HashFunction::Blake3, Some(b"other")),
+            hash(super::HashFunction::Blake3, Some(b"secret"))
+        );
+    }
+
+    #[test]
+    fn hash_function_from_str() {
+        for hash in [
+            super::HashFunction::Sha256,
+            super::HashFunction::Sha512,
+            super::HashFunction::Blake3,
+            super::HashFunction::Md5,
+        ] {
+            assert_eq!(Ok(hash), hash.to_string().parse());
+        }
+
+        assert_eq!(
+            Err("unsupported hash function 'sha1'".to_string()),
+            "sha1".parse::<super::HashFunction>()
+        );
+    }
+
+    #[test]
+    fn seed_from_digest() {
+        let generator = super::Seed::from_digest(vec![12, 13, 240, 4]).expect("empty digest");
+        assert_eq!(0, generator.position);
+        assert_eq!("0c0df004", generator.hash());
+
+        assert_eq!(None, super::Seed::from_digest(Vec::new()));
+    }
+
+    #[test]
+    fn color_display() {
+        assert_eq!("black", format!("{}", super::Color::Black));
+        assert_eq!("blue", format!("{}", super::Color::Blue));
+        assert_eq!("brown", format!("{}", super::Color::Brown));
+        assert_eq!("green", format!("{}", super::Color::Green));
+        assert_eq!("pink", format!("{}", super::Color::Pink));
+        assert_eq!("purple", format!("{}", super::Color::Purple));
+        assert_eq!("red", format!("{}", super::Color::Red));
+        assert_eq!("yellow", format!("{}", super::Color::Yellow));
+    }
+
     #[test]
     fn seed_iterator() {
         let generator = super::Seed {
@@ -1,4 +1,9 @@
+pub mod archive;
+pub mod batch;
 pub mod cli;
 pub mod error;
 pub mod generator;
+pub mod mesh;
+pub mod normalization;
 pub mod rendering;
+pub mod server;
@@ -0,0 +1,365 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extrusion {
+    /// Width and depth of a cell.
+    pub cell_size: f32,
+    /// Height of a filled cell above the base plate.
+    pub height: f32,
+    /// Thickness of the plate on which filled cells stand. No plate is generated when it is zero.
+    pub base: f32,
+}
+
+impl Default for Extrusion {
+    fn default() -> Self {
+        Self {
+            cell_size: 5.0,
+            height: 2.0,
+            base: 1.0,
+        }
+    }
+}
+
+/// Triangle mesh of a canva where each filled cell is extruded into a column standing on a base
+/// plate.
+///
+/// Neighbouring cells of the same height are merged into a single surface and every wall is split
+/// at the height of the base plate, so that each edge of the mesh is shared by exactly two
+/// triangles. Two filled cells touching only by a corner each get their own vertices along that
+/// corner, so that the mesh stays manifold.
+pub struct Mesh {
+    pub vertices: Vec<[f32; 3]>,
+    /// Indexes of the vertices of each triangle, in counter-clockwise order when seen from the
+    /// outside of the mesh.
+    pub triangles: Vec<[usize; 3]>,
+}
+
+// levels, in increasing order, at which vertices can be placed
+const GROUND: usize = 0;
+const BASE: usize = 1;
+const TOP: usize = 2;
+
+// position and level of a vertex, with the cell owning it when it is not shared
+type VertexKey = (usize, usize, usize, Option<(usize, usize)>);
+
+struct Builder {
+    extrusion: Extrusion,
+    // corners where only two diagonal cells rise above the others, with the lowest level from
+    // which the vertices of the corner belong to a single cell
+    pinches: std::collections::HashMap<(usize, usize), usize>,
+    // cell whose faces are being added
+    cell: (usize, usize),
+    indexes: std::collections::HashMap<VertexKey, usize>,
+    mesh: Mesh,
+}
+
+impl Builder {
+    fn vertex(&mut self, x: usize, y: usize, level: usize) -> usize {
+        let owner = self
+            .pinches
+            .get(&(x, y))
+            .filter(|from| level >= **from)
+            .map(|_| self.cell);
+        if let Some(index) = self.indexes.get(&(x, y, level, owner)) {
+            return *index;
+        }
+
+        let z = match level {
+            GROUND => 0.0,
+            BASE => self.extrusion.base,
+            _ => self.extrusion.base + self.extrusion.height,
+        };
+        let index = self.mesh.vertices.len();
+        self.mesh.vertices.push([
+            x as f32 * self.extrusion.cell_size,
+            y as f32 * self.extrusion.cell_size,
+            z,
+        ]);
+        self.indexes.insert((x, y, level, owner), index);
+
+        index
+    }
+
+    // corners are given as (x, y, level) in counter-clockwise order seen from the outside
+    fn quad(&mut self, corners: [(usize, usize, usize); 4]) {
+        let [a, b, c, d] = corners.map(|(x, y, level)| self.vertex(x, y, level));
+
+        self.mesh.triangles.push([a, b, c]);
+        self.mesh.triangles.push([a, c, d]);
+    }
+
+    // wall along the segment going from (x0, y0) to (x1, y1), facing right of this direction
+    fn wall(
+        &mut self,
+        (x0, y0): (usize, usize),
+        (x1, y1): (usize, usize),
+        low: usize,
+        high: usize,
+    ) {
+        (low..high).for_each(|level| {
+            self.quad([
+                (x0, y0, level),
+                (x1, y1, level),
+                (x1, y1, level + 1),
+                (x0, y0, level + 1),
+            ])
+        });
+    }
+}
+
+impl Mesh {
+    pub fn extrude(canva: crate::generator::Canva, extrusion: Extrusion) -> Self {
+        let size = canva.size();
+        let has_base = extrusion.base > 0.0;
+
+        let level = |x: isize, y: isize| -> usize {
+            if x < 0 || y < 0 {
+                return GROUND;
+            }
+
+            let pt = crate::generator::Point {
+                x: x as usize,
+                y: y as usize,
+            };
+            match canva.pixel(pt) {
+                Some(true) => TOP,
+                Some(false) if has_base => BASE,
+                _ => GROUND,
+            }
+        };
+
+        let mut pinches = std::collections::HashMap::new();
+        (0..=size as isize).for_each(|y| {
+            (0..=size as isize).for_each(|x| {
+                let (back_left, back_right) = (level(x - 1, y - 1), level(x, y - 1));
+                let (front_left, front_right) = (level(x - 1, y), level(x, y));
+
+                let low = if back_left.min(front_right) > back_right.max(front_left) {
+                    back_right.max(front_left)
+                } else if back_right.min(front_left) > back_left.max(front_right) {
+                    back_left.max(front_right)
+                } else {
+                    return;
+                };
+
+                // nothing else stands on the ground at this corner, so the bottom of the two
+                // cells is split too
+                let from = if low == GROUND { GROUND } else { low + 1 };
+                pinches.insert((x as usize, size - y as usize), from);
+            })
+        });
+
+        let mut builder = Builder {
+            extrusion,
+            pinches,
+            cell: (0, 0),
+            indexes: std::collections::HashMap::new(),
+            mesh: Mesh {
+                vertices: Vec::new(),
+                triangles: Vec::new(),
+            },
+        };
+
+        (0..size).for_each(|y| {
+            (0..size).for_each(|x| {
+                let current = level(x as isize, y as isize);
+                if current == GROUND {
+                    return;
+                }
+
+                // the first row of the canva is at the back of the mesh so that it reads the
+                // same way as the image when seen from above
+                let (x0, x1) = (x, x + 1);
+                let (y0, y1) = (size - y - 1, size - y);
+                builder.cell = (x, y);
+
+                builder.quad([
+                    (x0, y0, current),
+                    (x1, y0, current),
+                    (x1, y1, current),
+                    (x0, y1, current),
+                ]);
+                builder.quad([
+                    (x0, y0, GROUND),
+                    (x0, y1, GROUND),
+                    (x1, y1, GROUND),
+                    (x1, y0, GROUND),
+                ]);
+
+                let (x, y) = (x as isize, y as isize);
+                let right = level(x + 1, y);
+                if right < current {
+                    builder.wall((x1, y0), (x1, y1), right, current);
+                }
+                let left = level(x - 1, y);
+                if left < current {
+                    builder.wall((x0, y1), (x0, y0), left, current);
+                }
+                let back = level(x, y - 1);
+                if back < current {
+                    builder.wall((x1, y1), (x0, y1), back, current);
+                }
+                let front = level(x, y + 1);
+                if front < current {
+                    builder.wall((x0, y0), (x1, y0), front, current);
+                }
+            })
+        });
+
+        builder.mesh
+    }
+
+    /// Unit vector perpendicular to the triangle, pointing outside of the mesh. The normal of a
+    /// triangle without area is null.
+    pub fn normal(&self, triangle: [usize; 3]) -> [f32; 3] {
+        let [a, b, c] = triangle.map(|index| self.vertices[index]);
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let normal = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        let length = normal.iter().map(|n| n * n).sum::<f32>().sqrt();
+        if length == 0.0 {
+            return [0.0; 3];
+        }
+
+        normal.map(|n| n / length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn edges(mesh: &super::Mesh) -> std::collections::HashMap<(usize, usize), usize> {
+        let mut edges = std::collections::HashMap::new();
+        mesh.triangles.iter().for_each(|[a, b, c]| {
+            [(*a, *b), (*b, *c), (*c, *a)].into_iter().for_each(|edge| {
+                *edges.entry(edge).or_insert(0) += 1;
+            })
+        });
+
+        edges
+    }
+
+    fn volume(mesh: &super::Mesh) -> f32 {
+        mesh.triangles
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|index| mesh.vertices[index]);
+                (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+                    + a[2] * (b[0] * c[1] - b[1] * c[0]))
+                    / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn mesh_extrude_single_block() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(2, generator);
+
+        let mesh = super::Mesh::extrude(canva, super::Extrusion::default());
+
+        // the 2x2 canva is fully filled: a box whose sides are split at the base height
+        assert_eq!(9 + 9 + 8, mesh.vertices.len());
+        assert_eq!(8 * 2 + 8 * 2 * 2, mesh.triangles.len());
+        assert_eq!(10.0 * 10.0 * 3.0, volume(&mesh));
+    }
+
+    // whether two filled cells touch only by a corner
+    fn has_diagonal_contact(canva: &crate::generator::Canva) -> bool {
+        let filled =
+            |x: usize, y: usize| canva.pixel(crate::generator::Point { x, y }) == Some(true);
+
+        (1..canva.size()).any(|y| {
+            (1..canva.size()).any(|x| {
+                let (a, b) = (filled(x - 1, y - 1), filled(x, y - 1));
+                let (c, d) = (filled(x - 1, y), filled(x, y));
+                (a && d && !b && !c) || (b && c && !a && !d)
+            })
+        })
+    }
+
+    #[test]
+    fn mesh_extrude_is_closed_and_oriented() {
+        let canvas = [("hello", 5), ("hello", 10), ("world", 10), ("pixie", 10)].map(
+            |(word, size): (&str, usize)| {
+                let seed: crate::generator::Seed = word.into();
+                crate::generator::Canva::new(size, seed)
+            },
+        );
+        assert!(canvas.iter().any(has_diagonal_contact));
+
+        for canva in canvas {
+            for base in [1.0, 0.0] {
+                let extrusion = super::Extrusion {
+                    base,
+                    ..Default::default()
+                };
+                let mesh = super::Mesh::extrude(canva.clone(), extrusion);
+                let edges = edges(&mesh);
+
+                edges.iter().for_each(|((a, b), count)| {
+                    assert_eq!(1, *count, "edge {} -> {} is used more than once", a, b);
+                    assert_eq!(
+                        Some(&1),
+                        edges.get(&(*b, *a)),
+                        "edge {} -> {} has no opposite",
+                        a,
+                        b
+                    );
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn mesh_extrude_volume() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let filled = canva
+            .clone()
+            .into_iter()
+            .filter(|(_, shown)| *shown)
+            .count();
+
+        let mesh = super::Mesh::extrude(canva, super::Extrusion::default());
+
+        let expect = 25.0 * 25.0 * 1.0 + filled as f32 * 25.0 * 2.0;
+        assert!((expect - volume(&mesh)).abs() < 0.001);
+    }
+
+    #[test]
+    fn mesh_extrude_without_base() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let filled = canva
+            .clone()
+            .into_iter()
+            .filter(|(_, shown)| *shown)
+            .count();
+
+        let mesh = super::Mesh::extrude(
+            canva,
+            super::Extrusion {
+                base: 0.0,
+                ..Default::default()
+            },
+        );
+
+        let expect = filled as f32 * 25.0 * 2.0;
+        assert!((expect - volume(&mesh)).abs() < 0.001);
+        assert!(mesh.vertices.iter().all(|[_, _, z]| *z == 0.0 || *z == 2.0));
+    }
+
+    #[test]
+    fn mesh_normal() {
+        let mesh = super::Mesh {
+            vertices: vec![[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]],
+            triangles: vec![[0, 1, 2]],
+        };
+
+        assert_eq!([0.0, 0.0, 1.0], mesh.normal([0, 1, 2]));
+        assert_eq!([0.0, 0.0, -1.0], mesh.normal([0, 2, 1]));
+        assert_eq!([0.0, 0.0, 0.0], mesh.normal([0, 1, 1]));
+    }
+}
@@ -0,0 +1,241 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Transformation applied to a word before it is hashed into a seed, so that different spellings
+/// of the same identifier generate the same image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Removes the leading and trailing whitespaces.
+    Trim,
+    /// Converts to lowercase.
+    Lowercase,
+    /// Unicode canonical composition.
+    Nfc,
+    /// Unicode compatibility composition.
+    Nfkc,
+    /// Unicode full case folding, which also maps characters such as 'ß' to "ss".
+    Casefold,
+    /// Canonical form of an email address.
+    Email,
+    /// Lowercase hyphenated form of an UUID.
+    Uuid,
+}
+
+impl std::fmt::Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Trim => "trim",
+            Self::Lowercase => "lowercase",
+            Self::Nfc => "nfc",
+            Self::Nfkc => "nfkc",
+            Self::Casefold => "casefold",
+            Self::Email => "email",
+            Self::Uuid => "uuid",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for Normalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trim" => Ok(Self::Trim),
+            "lowercase" => Ok(Self::Lowercase),
+            "nfc" => Ok(Self::Nfc),
+            "nfkc" => Ok(Self::Nfkc),
+            "casefold" => Ok(Self::Casefold),
+            "email" => Ok(Self::Email),
+            "uuid" => Ok(Self::Uuid),
+            value => Err(format!("unsupported normalization '{}'", value)),
+        }
+    }
+}
+
+impl Normalization {
+    pub fn apply(&self, value: &str) -> Result<String, crate::error::Error> {
+        match self {
+            Self::Trim => Ok(value.trim().to_string()),
+            Self::Lowercase => Ok(value.to_lowercase()),
+            Self::Nfc => Ok(value.nfc().collect()),
+            Self::Nfkc => Ok(value.nfkc().collect()),
+            Self::Casefold => Ok(caseless::default_case_fold_str(value)),
+            Self::Email => email(value),
+            Self::Uuid => uuid::Uuid::parse_str(value.trim())
+                .map(|uuid| uuid.hyphenated().to_string())
+                .map_err(|_| crate::error::Error::generic(format!("invalid uuid '{}'", value))),
+        }
+    }
+}
+
+/// Applies the normalizations in order.
+pub fn normalize(
+    value: &str,
+    normalizations: &[Normalization],
+) -> Result<String, crate::error::Error> {
+    normalizations
+        .iter()
+        .try_fold(value.to_string(), |value, normalization| {
+            normalization.apply(&value)
+        })
+}
+
+// providers ignoring the dots of the local part of their addresses
+const DOTLESS_DOMAINS: &[&str] = &["gmail.com"];
+
+// providers delivering local+tag@domain to local@domain
+const SUBADDRESSING_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "outlook.com",
+    "hotmail.com",
+    "live.com",
+    "icloud.com",
+    "me.com",
+    "mac.com",
+    "fastmail.com",
+    "protonmail.com",
+    "proton.me",
+];
+
+// lowercases the address, and removes the parts which some providers ignore when delivering
+fn email(value: &str) -> Result<String, crate::error::Error> {
+    let address = value.trim().to_lowercase();
+    let invalid = || crate::error::Error::generic(format!("invalid email '{}'", value));
+
+    let (local, domain) = address.rsplit_once('@').ok_or_else(invalid)?;
+    let domain = domain.trim_end_matches('.');
+    let domain = if domain == "googlemail.com" {
+        "gmail.com"
+    } else {
+        domain
+    };
+    if local.is_empty() || domain.is_empty() || domain.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+
+    let mut local = local.to_string();
+    if SUBADDRESSING_DOMAINS.contains(&domain) {
+        if let Some((base, _)) = local.split_once('+') {
+            local = base.to_string();
+        }
+    }
+    if DOTLESS_DOMAINS.contains(&domain) {
+        local.retain(|c| c != '.');
+    }
+    if local.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(format!("{}@{}", local, domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Normalization;
+
+    #[test]
+    fn normalization_from_str() {
+        for normalization in [
+            Normalization::Trim,
+            Normalization::Lowercase,
+            Normalization::Nfc,
+            Normalization::Nfkc,
+            Normalization::Casefold,
+            Normalization::Email,
+            Normalization::Uuid,
+        ] {
+            assert_eq!(Ok(normalization), normalization.to_string().parse());
+        }
+
+        assert_eq!(
+            Err("unsupported normalization 'upper'".to_string()),
+            "upper".parse::<Normalization>()
+        );
+    }
+
+    #[test]
+    fn normalization_apply() {
+        let apply = |normalization: Normalization, value| {
+            normalization
+                .apply(value)
+                .expect("failed to normalize value")
+        };
+
+        assert_eq!("Alice", apply(Normalization::Trim, " \tAlice\n"));
+        assert_eq!(
+            "alice straße",
+            apply(Normalization::Lowercase, "ALICE Straße")
+        );
+        assert_eq!(
+            "alice strasse",
+            apply(Normalization::Casefold, "ALICE Straße")
+        );
+        assert_eq!("caf\u{e9}", apply(Normalization::Nfc, "cafe\u{301}"));
+        assert_eq!("fi2", apply(Normalization::Nfkc, "\u{fb01}\u{b2}"));
+        assert_eq!(
+            "\u{fb01}\u{b2}",
+            apply(Normalization::Nfc, "\u{fb01}\u{b2}")
+        );
+    }
+
+    #[test]
+    fn normalization_email() {
+        let email = |value| {
+            Normalization::Email
+                .apply(value)
+                .expect("failed to normalize email")
+        };
+
+        assert_eq!("alice@example.com", email(" Alice@Example.com "));
+        assert_eq!("alice+news@example.com", email("alice+news@example.com"));
+        assert_eq!("a.lice@example.com", email("a.lice@example.com"));
+        assert_eq!("alice@gmail.com", email("A.Lice+News@GoogleMail.com"));
+        assert_eq!("a.lice@outlook.com", email("a.lice+news@outlook.com"));
+        assert_eq!("alice@example.com", email("alice@example.com."));
+        assert_eq!("\"a@b\"@example.com", email("\"a@b\"@example.com"));
+
+        for invalid in ["alice", "@example.com", "alice@", "+news@gmail.com"] {
+            assert!(Normalization::Email.apply(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn normalization_uuid() {
+        let expect = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+        for value in [
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "67E55044-10B1-426F-9247-BB680E5FE0C8",
+            "67e5504410b1426f9247bb680e5fe0c8",
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+            "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+            " 67e55044-10b1-426f-9247-bb680e5fe0c8\n",
+        ] {
+            assert_eq!(
+                expect,
+                Normalization::Uuid
+                    .apply(value)
+                    .expect("failed to normalize uuid")
+            );
+        }
+
+        assert!(Normalization::Uuid.apply("not-a-uuid").is_err());
+    }
+
+    #[test]
+    fn normalize() {
+        let normalizations = [Normalization::Trim, Normalization::Casefold];
+
+        assert_eq!(
+            "alice@example.com",
+            super::normalize(" ALICE@example.COM ", &normalizations)
+                .expect("failed to normalize value")
+        );
+        assert_eq!(
+            "Alice ",
+            super::normalize("Alice ", &[]).expect("failed to normalize value")
+        );
+        assert!(super::normalize("alice", &[Normalization::Trim, Normalization::Email]).is_err());
+    }
+}
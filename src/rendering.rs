@@ -1,6 +1,155 @@
-pub struct Terminal;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guess the colour depth supported by the terminal from the values of the `COLORTERM` and
+    /// `TERM` environment variables. Terminals which do not advertise anything are assumed to
+    /// support 256 colours.
+    pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if let Some("truecolor" | "24bit") = colorterm {
+            return Self::TrueColor;
+        }
+
+        match term {
+            None => Self::Ansi256,
+            Some(term) if term.ends_with("-direct") || term.ends_with("-truecolor") => {
+                Self::TrueColor
+            }
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some(_) => Self::Ansi16,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+
+        Self::detect(colorterm.as_deref(), term.as_deref())
+    }
+}
+
+impl std::fmt::Display for ColorDepth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Ansi16 => "16",
+            Self::Ansi256 => "256",
+            Self::TrueColor => "truecolor",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for ColorDepth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "16" => Ok(Self::Ansi16),
+            "256" => Ok(Self::Ansi256),
+            "truecolor" => Ok(Self::TrueColor),
+            value => Err(format!("unsupported color depth '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border {
+    None,
+    Single,
+    Double,
+    Rounded,
+    Heavy,
+}
+
+impl Border {
+    // top left, top right, bottom left, bottom right, horizontal and vertical characters
+    fn characters(&self) -> Option<[char; 6]> {
+        match self {
+            Self::None => None,
+            Self::Single => Some(['┌', '┐', '└', '┘', '─', '│']),
+            Self::Double => Some(['╔', '╗', '╚', '╝', '═', '║']),
+            Self::Rounded => Some(['╭', '╮', '╰', '╯', '─', '│']),
+            Self::Heavy => Some(['┏', '┓', '┗', '┛', '━', '┃']),
+        }
+    }
+}
+
+impl std::fmt::Display for Border {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::None => "none",
+            Self::Single => "single",
+            Self::Double => "double",
+            Self::Rounded => "rounded",
+            Self::Heavy => "heavy",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for Border {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "single" => Ok(Self::Single),
+            "double" => Ok(Self::Double),
+            "rounded" => Ok(Self::Rounded),
+            "heavy" => Ok(Self::Heavy),
+            value => Err(format!("unsupported border style '{}'", value)),
+        }
+    }
+}
+
+pub struct Terminal {
+    pub color_depth: ColorDepth,
+    pub border: Border,
+    /// Number of blank characters between the drawing and the left and right sides of the frame.
+    /// Since a terminal character is about twice as high as it is wide, half as many blank lines
+    /// are added above and below the drawing.
+    pub padding: usize,
+    pub background: bool,
+}
+
+impl Default for Terminal {
+    fn default() -> Self {
+        Self {
+            color_depth: ColorDepth::Ansi256,
+            border: Border::Single,
+            padding: 1,
+            background: true,
+        }
+    }
+}
 
 impl Terminal {
+    // RGB values of the basic 16 colours, as defined by xterm
+    const ANSI_16_PALETTE: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+
     fn ansi_color(color: crate::generator::Color) -> usize {
         match color {
             crate::generator::Color::Red => 160,
@@ -14,34 +163,675 @@ impl Terminal {
         }
     }
 
-    pub fn render<W: std::io::Write>(&mut self, mut w: W, canva: crate::generator::Canva) {
-        let line = "──".repeat(canva.size());
-        let color = Self::ansi_color(canva.color());
+    fn nearest_ansi_16(rgb: [u8; 3]) -> usize {
+        let distance = |other: &[u8; 3]| -> u32 {
+            rgb.iter()
+                .zip(other.iter())
+                .map(|(a, b)| (*a as i32 - *b as i32).pow(2) as u32)
+                .sum()
+        };
+
+        Self::ANSI_16_PALETTE
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, other)| distance(other))
+            .map(|(index, _)| index)
+            .expect("palette should never be empty")
+    }
+
+    fn foreground(&self, color: crate::generator::Color) -> String {
+        let rgb = Png::rgb_color(color).0;
+        match self.color_depth {
+            ColorDepth::Ansi16 => match Self::nearest_ansi_16(rgb) {
+                index @ 0..=7 => format!("{}", 30 + index),
+                index => format!("{}", 90 + index - 8),
+            },
+            ColorDepth::Ansi256 => format!("38;5;{}", Self::ansi_color(color)),
+            ColorDepth::TrueColor => format!("38;2;{};{};{}", rgb[0], rgb[1], rgb[2]),
+        }
+    }
 
-        writeln!(w, "\x1b[38;5;{};48;5;15m┌─{}─┐\x1b[0m", color, line)
-            .expect("failed to write top border");
-        write!(w, "\x1b[38;5;{};48;5;15m│ ", color).expect("failed to write beginning of drawing");
+    fn background(&self) -> String {
+        match self.color_depth {
+            ColorDepth::Ansi16 => "107".to_string(),
+            ColorDepth::Ansi256 => "48;5;15".to_string(),
+            ColorDepth::TrueColor => "48;2;255;255;255".to_string(),
+        }
+    }
+
+    pub fn render<W: std::io::Write>(&mut self, mut w: W, canva: crate::generator::Canva) {
+        let style = if self.background {
+            format!(
+                "\x1b[{};{}m",
+                self.foreground(canva.color()),
+                self.background()
+            )
+        } else {
+            format!("\x1b[{}m", self.foreground(canva.color()))
+        };
+        let size = canva.size();
+        let width = 2 * size + 2 * self.padding;
+        let side_padding = " ".repeat(self.padding);
 
-        let mut current_line = 0;
+        let mut lines = vec![" ".repeat(width); self.padding / 2];
+        let mut current_line = String::new();
         canva.into_iter().for_each(|(pt, shown)| {
-            if pt.y > current_line {
-                current_line = pt.y;
-                writeln!(w, " │\x1b[0m").expect("failed to write end of line");
-                write!(w, "\x1b[38;5;{};48;5;15m│ ", color).expect("failed to write end of line");
+            if pt.x == 0 {
+                current_line.push_str(&side_padding);
+            }
+
+            current_line.push_str(if shown { "██" } else { "  " });
+
+            if pt.x + 1 == size {
+                current_line.push_str(&side_padding);
+                lines.push(std::mem::take(&mut current_line));
+            }
+        });
+        lines.extend(vec![" ".repeat(width); self.padding / 2]);
+
+        match self.border.characters() {
+            Some([top_left, top_right, bottom_left, bottom_right, horizontal, vertical]) => {
+                let line = horizontal.to_string().repeat(width);
+                writeln!(w, "{}{}{}{}\x1b[0m", style, top_left, line, top_right)
+                    .expect("failed to write top border");
+                lines.iter().for_each(|content| {
+                    writeln!(w, "{}{}{}{}\x1b[0m", style, vertical, content, vertical)
+                        .expect("failed to write line");
+                });
+                writeln!(w, "{}{}{}{}\x1b[0m", style, bottom_left, line, bottom_right)
+                    .expect("failed to write bottom border");
+            }
+            None => lines.iter().for_each(|content| {
+                writeln!(w, "{}{}\x1b[0m", style, content).expect("failed to write line");
+            }),
+        }
+    }
+}
+
+/// Text made of colored square emojis, one per cell. Unicode has no pink square, so pink and
+/// purple canvas are both drawn with the purple square.
+pub struct Emoji;
+
+impl Emoji {
+    fn square(color: crate::generator::Color) -> char {
+        match color {
+            crate::generator::Color::Red => '🟥',
+            crate::generator::Color::Blue => '🟦',
+            crate::generator::Color::Pink => '🟪',
+            crate::generator::Color::Green => '🟩',
+            crate::generator::Color::Brown => '🟫',
+            crate::generator::Color::Purple => '🟪',
+            crate::generator::Color::Yellow => '🟨',
+            crate::generator::Color::Black => '⬛',
+        }
+    }
+
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let size = canva.size();
+        let square = Self::square(canva.color());
+
+        canva.into_iter().try_for_each(|(pt, shown)| {
+            write!(w, "{}", if shown { square } else { '⬜' })?;
+            if pt.x + 1 == size {
+                writeln!(w)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+fn hex_color(color: crate::generator::Color) -> String {
+    let [r, g, b] = Png::rgb_color(color).0;
+
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Color given as `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexColor(pub [u8; 3]);
+
+impl std::fmt::Display for HexColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [r, g, b] = self.0;
+
+        write!(f, "#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl std::str::FromStr for HexColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("unsupported color '{}'", s);
+        let hex = s.strip_prefix('#').ok_or_else(invalid)?;
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let channel =
+            |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid());
+
+        Ok(Self([channel(0)?, channel(2)?, channel(4)?]))
+    }
+}
+
+pub struct Html {
+    pub cell_size: u32,
+}
+
+impl Default for Html {
+    fn default() -> Self {
+        Self { cell_size: 10 }
+    }
+}
+
+impl Html {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let size = canva.size();
+        let margin = self.cell_size / 2;
+        let color = hex_color(canva.color());
+
+        writeln!(
+            w,
+            "<table cellpadding=\"0\" cellspacing=\"0\" border=\"0\" style=\"border-collapse:collapse;border:{}px solid #ffffff;background-color:#ffffff\">",
+            margin
+        )?;
+        canva.into_iter().try_for_each(|(pt, shown)| {
+            if pt.x == 0 {
+                write!(w, "<tr>")?;
+            }
+
+            let background = if shown {
+                format!(";background-color:{}", color)
+            } else {
+                String::new()
+            };
+            write!(
+                w,
+                "<td style=\"width:{size}px;height:{size}px;padding:0{}\"></td>",
+                background,
+                size = self.cell_size
+            )?;
+
+            if pt.x + 1 == size {
+                writeln!(w, "</tr>")?;
             }
 
-            let pattern = if shown { "██" } else { "  " };
-            write!(w, "{}", pattern).expect("failed to write character");
+            Ok::<(), crate::error::Error>(())
+        })?;
+        writeln!(w, "</table>")?;
+
+        Ok(())
+    }
+}
+
+pub struct Css {
+    pub selector: String,
+    pub cell_size: u32,
+}
+
+impl Default for Css {
+    fn default() -> Self {
+        Self {
+            selector: ".pixie".to_string(),
+            cell_size: 10,
+        }
+    }
+}
+
+impl Css {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        // a box-shadow is never painted under the element casting it, so the element is kept as
+        // a single cell in the top left corner and every filled cell is shifted by one cell
+        let drawing_size = self.cell_size * canva.size() as u32;
+        let color = hex_color(canva.color());
+
+        let shadows: Vec<String> = canva
+            .into_iter()
+            .filter(|(_, displayed)| *displayed)
+            .map(|(pt, _)| {
+                format!(
+                    "{}px {}px {}",
+                    (pt.x as u32 + 1) * self.cell_size,
+                    (pt.y as u32 + 1) * self.cell_size,
+                    color
+                )
+            })
+            .collect();
+        let shadows = if shadows.is_empty() {
+            "none".to_string()
+        } else {
+            shadows.join(",\n    ")
+        };
+
+        writeln!(w, "{} {{", self.selector)?;
+        writeln!(w, "  width: {}px;", self.cell_size)?;
+        writeln!(w, "  height: {}px;", self.cell_size)?;
+        writeln!(w, "  margin: 0 {}px {}px 0;", drawing_size, drawing_size)?;
+        writeln!(w, "  box-shadow:\n    {};", shadows)?;
+        writeln!(w, "}}")?;
+
+        Ok(())
+    }
+}
+
+pub struct Json {
+    /// Hexadecimal representation of the hash of the seed used to generate the canva.
+    pub hash: String,
+}
+
+impl Json {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let size = canva.size();
+        let color = canva.color();
+        let [r, g, b] = Png::rgb_color(color).0;
+
+        let cells: Vec<bool> = canva.into_iter().map(|(_, shown)| shown).collect();
+        let grid: Vec<&[bool]> = cells.chunks(size.max(1)).collect();
+        let bits: String = cells
+            .iter()
+            .map(|shown| if *shown { '1' } else { '0' })
+            .collect();
+
+        let document = serde_json::json!({
+            "version": crate::generator::ALGORITHM_VERSION,
+            "size": size,
+            "hash": self.hash,
+            "color": {
+                "name": color.to_string(),
+                "rgb": [r, g, b],
+                "hex": hex_color(color),
+            },
+            "grid": grid,
+            "bits": bits,
         });
-        writeln!(w, " │\x1b[0m").expect("failed to write end of drawing");
-        writeln!(w, "\x1b[38;5;{};48;5;15m└─{}─┘\x1b[0m", color, line)
-            .expect("failed to write bottom border");
+
+        serde_json::to_writer_pretty(&mut w, &document).map_err(std::io::Error::from)?;
+        writeln!(w)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Mask {
+    #[default]
+    Square,
+    Circle,
+    /// Square with rounded corners, the radius being a fraction, between 0 and 1, of half the
+    /// side of the image.
+    Rounded {
+        radius: f32,
+    },
+    Squircle,
+}
+
+impl Mask {
+    // number of samples taken on each axis of a pixel to compute its coverage
+    const SAMPLES: u32 = 4;
+
+    // whether a point, whose coordinates go from -1 to 1 across the image, is visible
+    fn contains(&self, u: f32, v: f32) -> bool {
+        match self {
+            Self::Square => u.abs() <= 1.0 && v.abs() <= 1.0,
+            Self::Circle => u * u + v * v <= 1.0,
+            Self::Rounded { radius } => {
+                let radius = radius.clamp(0.0, 1.0);
+                let du = u.abs() - (1.0 - radius);
+                let dv = v.abs() - (1.0 - radius);
+
+                u.abs() <= 1.0
+                    && v.abs() <= 1.0
+                    && (du <= 0.0 || dv <= 0.0 || du * du + dv * dv <= radius * radius)
+            }
+            Self::Squircle => u.powi(4) + v.powi(4) <= 1.0,
+        }
+    }
+
+    /// Fraction, between 0 and 1, of the pixel at (x, y) which is visible through the mask.
+    fn coverage(&self, x: u32, y: u32, image_size: u32) -> f32 {
+        let half = image_size as f32 / 2.0;
+        let step = 1.0 / Self::SAMPLES as f32;
+
+        let visible = (0..Self::SAMPLES)
+            .flat_map(|i| (0..Self::SAMPLES).map(move |j| (i, j)))
+            .filter(|(i, j)| {
+                let u = (x as f32 + (*i as f32 + 0.5) * step - half) / half;
+                let v = (y as f32 + (*j as f32 + 0.5) * step - half) / half;
+                self.contains(u, v)
+            })
+            .count();
+
+        visible as f32 / (Self::SAMPLES * Self::SAMPLES) as f32
+    }
+
+    // SVG element describing the visible area of an image of the given size
+    fn svg_clip_path(&self, image_size: usize) -> Option<String> {
+        let half = image_size as f32 / 2.0;
+
+        match self {
+            Self::Square => None,
+            Self::Circle => Some(format!(
+                "<circle cx=\"{half}\" cy=\"{half}\" r=\"{half}\"/>",
+                half = half
+            )),
+            Self::Rounded { radius } => Some(format!(
+                "<rect width=\"{size}\" height=\"{size}\" rx=\"{radius}\" ry=\"{radius}\"/>",
+                size = image_size,
+                radius = radius.clamp(0.0, 1.0) * half
+            )),
+            Self::Squircle => {
+                let steps = 64;
+                let points: Vec<String> = (0..steps)
+                    .map(|step| {
+                        let angle = step as f32 * std::f32::consts::TAU / steps as f32;
+                        let (sin, cos) = angle.sin_cos();
+                        let x = half + half * cos.signum() * cos.abs().sqrt();
+                        let y = half + half * sin.signum() * sin.abs().sqrt();
+                        format!("{:.2},{:.2}", x, y)
+                    })
+                    .collect();
+
+                Some(format!("<polygon points=\"{}\"/>", points.join(" ")))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellShape {
+    #[default]
+    Square,
+    Dot,
+    Diamond,
+    Rounded,
+    /// Half of the cell, cut along a diagonal so that the triangle points towards the center of
+    /// the canva.
+    Triangle,
+}
+
+impl std::fmt::Display for CellShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Square => "square",
+            Self::Dot => "dot",
+            Self::Diamond => "diamond",
+            Self::Rounded => "rounded",
+            Self::Triangle => "triangle",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for CellShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "square" => Ok(Self::Square),
+            "dot" => Ok(Self::Dot),
+            "diamond" => Ok(Self::Diamond),
+            "rounded" => Ok(Self::Rounded),
+            "triangle" => Ok(Self::Triangle),
+            value => Err(format!("unsupported cell shape '{}'", value)),
+        }
+    }
+}
+
+impl CellShape {
+    // -1 when the index is before the middle of the canva, 1 when it is after and 0 when it is
+    // exactly on the middle
+    fn side(index: usize, size: usize) -> i8 {
+        match (2 * index + 1).cmp(&size) {
+            std::cmp::Ordering::Less => -1,
+            std::cmp::Ordering::Equal => 0,
+            std::cmp::Ordering::Greater => 1,
+        }
+    }
+
+    /// Area drawn for the cell at `pt` of a canva of `size` cells, the cell being drawn in the
+    /// square starting at (x, y) of side `width`.
+    fn primitive(
+        &self,
+        pt: crate::generator::Point,
+        size: usize,
+        (x, y): (f32, f32),
+        width: f32,
+    ) -> Primitive {
+        let (x0, y0, x1, y1) = (x, y, x + width, y + width);
+        let (xm, ym) = (x + width / 2.0, y + width / 2.0);
+        let diamond = vec![(xm, y0), (x1, ym), (xm, y1), (x0, ym)];
+
+        match self {
+            Self::Square => Primitive::Rect { x, y, width },
+            Self::Dot => Primitive::Circle {
+                cx: xm,
+                cy: ym,
+                radius: width / 2.0,
+            },
+            Self::Diamond => Primitive::Polygon(diamond),
+            Self::Rounded => Primitive::RoundedRect {
+                x,
+                y,
+                width,
+                radius: width / 4.0,
+            },
+            Self::Triangle => {
+                Primitive::Polygon(match (Self::side(pt.x, size), Self::side(pt.y, size)) {
+                    (-1, -1) => vec![(x0, y0), (x1, y0), (x0, y1)],
+                    (1, -1) => vec![(x0, y0), (x1, y0), (x1, y1)],
+                    (-1, 1) => vec![(x0, y0), (x1, y1), (x0, y1)],
+                    (1, 1) => vec![(x1, y0), (x1, y1), (x0, y1)],
+                    (0, -1) => vec![(x0, y0), (x1, y0), (xm, y1)],
+                    (0, 1) => vec![(xm, y0), (x1, y1), (x0, y1)],
+                    (-1, 0) => vec![(x0, y0), (x1, ym), (x0, y1)],
+                    (1, 0) => vec![(x1, y0), (x1, y1), (x0, ym)],
+                    _ => diamond,
+                })
+            }
+        }
+    }
+}
+
+// geometry of a filled cell, in image coordinates
+#[derive(Debug, Clone, PartialEq)]
+enum Primitive {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+    },
+    Circle {
+        cx: f32,
+        cy: f32,
+        radius: f32,
+    },
+    RoundedRect {
+        x: f32,
+        y: f32,
+        width: f32,
+        radius: f32,
+    },
+    Polygon(Vec<(f32, f32)>),
+}
+
+impl Primitive {
+    fn contains(&self, px: f32, py: f32) -> bool {
+        match self {
+            Self::Rect { x, y, width } => px >= *x && px < x + width && py >= *y && py < y + width,
+            Self::Circle { cx, cy, radius } => {
+                (px - cx).powi(2) + (py - cy).powi(2) <= radius.powi(2)
+            }
+            Self::RoundedRect {
+                x,
+                y,
+                width,
+                radius,
+            } => {
+                // distance to the square whose corners are the centers of the rounded corners
+                let dx = (x + radius - px).max(px - (x + width - radius)).max(0.0);
+                let dy = (y + radius - py).max(py - (y + width - radius)).max(0.0);
+
+                px >= *x
+                    && px < x + width
+                    && py >= *y
+                    && py < y + width
+                    && dx * dx + dy * dy <= radius * radius
+            }
+            Self::Polygon(points) => {
+                // the polygons are convex: the point is on the same side of every edge
+                let sides: Vec<f32> = points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|((x0, y0), (x1, y1))| (x1 - x0) * (py - y0) - (y1 - y0) * (px - x0))
+                    .collect();
+
+                sides.iter().all(|side| *side >= 0.0) || sides.iter().all(|side| *side <= 0.0)
+            }
+        }
+    }
+
+    fn draw(&self, img: &mut image::RgbImage, color: image::Rgb<u8>) {
+        let rect = |x: f32, y: f32, width: f32, height: f32| {
+            imageproc::rect::Rect::at(x.round() as i32, y.round() as i32)
+                .of_size(width.round() as u32, height.round() as u32)
+        };
+
+        match self {
+            Self::Rect { x, y, width } => {
+                imageproc::drawing::draw_filled_rect_mut(img, rect(*x, *y, *width, *width), color)
+            }
+            Self::Circle { cx, cy, radius } => {
+                // the circle covers the pixels at the center plus or minus the radius
+                imageproc::drawing::draw_filled_circle_mut(
+                    img,
+                    ((cx - 0.5).round() as i32, (cy - 0.5).round() as i32),
+                    (radius - 0.5).round() as i32,
+                    color,
+                )
+            }
+            Self::RoundedRect {
+                x,
+                y,
+                width,
+                radius,
+            } => {
+                let inner = width - 2.0 * radius;
+                imageproc::drawing::draw_filled_rect_mut(
+                    img,
+                    rect(*x + radius, *y, inner, *width),
+                    color,
+                );
+                imageproc::drawing::draw_filled_rect_mut(
+                    img,
+                    rect(*x, *y + radius, *width, inner),
+                    color,
+                );
+                [
+                    (x + radius, y + radius),
+                    (x + width - radius, y + radius),
+                    (x + radius, y + width - radius),
+                    (x + width - radius, y + width - radius),
+                ]
+                .into_iter()
+                .for_each(|(cx, cy)| {
+                    imageproc::drawing::draw_filled_circle_mut(
+                        img,
+                        ((cx - 0.5).round() as i32, (cy - 0.5).round() as i32),
+                        (radius - 0.5).round() as i32,
+                        color,
+                    )
+                });
+            }
+            Self::Polygon(points) => {
+                // the last row and column of pixels of a cell start one pixel before its edge
+                let (left, top) = points
+                    .iter()
+                    .fold((f32::MAX, f32::MAX), |(left, top), (x, y)| {
+                        (left.min(*x), top.min(*y))
+                    });
+                let points: Vec<imageproc::point::Point<i32>> = points
+                    .iter()
+                    .map(|(x, y)| {
+                        let x = if *x > left { x - 1.0 } else { *x };
+                        let y = if *y > top { y - 1.0 } else { *y };
+                        imageproc::point::Point::new(x.round() as i32, y.round() as i32)
+                    })
+                    .collect();
+                imageproc::drawing::draw_polygon_mut(img, &points, color)
+            }
+        }
+    }
+
+    fn svg(&self) -> String {
+        match self {
+            Self::Rect { x, y, width } => format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{size}\" height=\"{size}\"/>",
+                x,
+                y,
+                size = width
+            ),
+            Self::Circle { cx, cy, radius } => {
+                format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>", cx, cy, radius)
+            }
+            Self::RoundedRect {
+                x,
+                y,
+                width,
+                radius,
+            } => format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{size}\" height=\"{size}\" rx=\"{radius}\" ry=\"{radius}\"/>",
+                x,
+                y,
+                size = width,
+                radius = radius
+            ),
+            Self::Polygon(points) => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|(x, y)| format!("{},{}", x, y))
+                    .collect();
+                format!("<polygon points=\"{}\"/>", points.join(" "))
+            }
+        }
     }
 }
 
-pub struct Png;
+#[derive(Default)]
+pub struct Png {
+    pub mask: Mask,
+    pub cell_shape: CellShape,
+    /// Space left empty between neighbouring cells, in pixels.
+    pub gap: u32,
+    /// Color of the lines drawn along the border of every cell, if any.
+    pub grid_lines: Option<HexColor>,
+    /// Width and height of the image, in pixels. When set, the cells are rasterized with
+    /// sub-pixel accuracy instead of being aligned on a grid of 50 pixels.
+    pub image_size: Option<u32>,
+}
 
 impl Png {
+    // samples per pixel along each axis when rasterizing at an arbitrary size
+    const SAMPLES: u32 = 4;
+
     fn rgb_color(color: crate::generator::Color) -> image::Rgb<u8> {
         match color {
             crate::generator::Color::Red => image::Rgb([222, 48, 48]),
@@ -55,17 +845,89 @@ impl Png {
         }
     }
 
-    pub fn render<W: std::io::Write + std::io::Seek>(
-        &mut self,
-        mut w: W,
+    fn image(&self, canva: crate::generator::Canva) -> image::RgbImage {
+        self.image_with_pixel_size(canva, 50)
+    }
+
+    /// Renders the canva on an image of exactly `image_size` by `image_size` pixels. Cells do not
+    /// have to start on a pixel boundary: each pixel is supersampled and gets the average color of
+    /// its samples, so that every cell has the same size whatever the scale.
+    fn sized_image(&self, canva: crate::generator::Canva, image_size: u32) -> image::RgbImage {
+        let size = canva.size();
+        let pixel_size = image_size as f32 / (size + 1) as f32;
+        let margin = pixel_size / 2.0;
+        let length = pixel_size * size as f32;
+        let color = Self::rgb_color(canva.color());
+        let gap = self.gap as f32;
+
+        let mut primitives = vec![None; size * size];
+        canva
+            .into_iter()
+            .filter(|(_, displayed)| *displayed)
+            .for_each(|(pt, _)| {
+                let x = margin + pt.x as f32 * pixel_size + gap / 2.0;
+                let y = margin + pt.y as f32 * pixel_size + gap / 2.0;
+                let width = (pixel_size - gap).max(0.0);
+                primitives[pt.y * size + pt.x] =
+                    Some(self.cell_shape.primitive(pt, size, (x, y), width));
+            });
+
+        // grid lines are one pixel wide and start on the border of the cells
+        let on_grid_line = |position: f32, other: f32| {
+            let index = ((position - margin) / pixel_size).round();
+            let offset = position - (margin + index * pixel_size);
+
+            (0.0..=size as f32).contains(&index)
+                && (0.0..1.0).contains(&offset)
+                && (margin..margin + length).contains(&other)
+        };
+
+        let sample = |sx: f32, sy: f32| -> [u8; 3] {
+            if let Some(grid_color) = self.grid_lines {
+                if on_grid_line(sx, sy) || on_grid_line(sy, sx) {
+                    return grid_color.0;
+                }
+            }
+
+            let cx = ((sx - margin) / pixel_size).floor();
+            let cy = ((sy - margin) / pixel_size).floor();
+            if cx < 0.0 || cy < 0.0 || cx >= size as f32 || cy >= size as f32 {
+                return [255, 255, 255];
+            }
+
+            match &primitives[cy as usize * size + cx as usize] {
+                Some(primitive) if primitive.contains(sx, sy) => color.0,
+                _ => [255, 255, 255],
+            }
+        };
+
+        let step = 1.0 / Self::SAMPLES as f32;
+        let count = Self::SAMPLES * Self::SAMPLES;
+        image::RgbImage::from_fn(image_size, image_size, |x, y| {
+            let sum = (0..Self::SAMPLES)
+                .flat_map(|i| (0..Self::SAMPLES).map(move |j| (i, j)))
+                .map(|(i, j)| {
+                    sample(
+                        x as f32 + (i as f32 + 0.5) * step,
+                        y as f32 + (j as f32 + 0.5) * step,
+                    )
+                })
+                .fold([0u32; 3], |[r, g, b], [sr, sg, sb]| {
+                    [r + sr as u32, g + sg as u32, b + sb as u32]
+                });
+
+            image::Rgb(sum.map(|channel| ((channel + count / 2) / count) as u8))
+        })
+    }
+
+    fn image_with_pixel_size(
+        &self,
         canva: crate::generator::Canva,
-    ) -> Result<(), crate::error::Error> {
-        let pixel_size = 50;
+        pixel_size: usize,
+    ) -> image::RgbImage {
         let margin = pixel_size / 2;
-        let image_size = (pixel_size * canva.size() + (margin * 2)) as u32;
-
-        let pixel_size = pixel_size as u32;
-        let margin = margin as i32;
+        let size = canva.size();
+        let image_size = (pixel_size * size + (margin * 2)) as u32;
         let color = Self::rgb_color(canva.color());
 
         let mut img = image::RgbImage::new(image_size, image_size);
@@ -77,31 +939,1881 @@ impl Png {
             .filter(|(_, displayed)| *displayed)
             .map(|(pt, _)| pt)
             .for_each(|pt| {
-                let x = margin + (pt.x as i32 * pixel_size as i32);
-                let y = margin + (pt.y as i32 * pixel_size as i32);
-                let rect = imageproc::rect::Rect::at(x, y).of_size(pixel_size, pixel_size);
-                imageproc::drawing::draw_filled_rect_mut(&mut img, rect, color);
+                let (x, y, width) = cell_bounds(pt, margin, pixel_size, self.gap);
+                self.cell_shape
+                    .primitive(pt, size, (x, y), width)
+                    .draw(&mut img, color);
             });
 
-        img.write_to(&mut w, image::ImageOutputFormat::Png)?;
-        Ok(())
+        if let Some(grid_color) = self.grid_lines {
+            let length = (pixel_size * size) as u32;
+            (0..=size).for_each(|index| {
+                let offset = (margin + index * pixel_size) as i32;
+                let margin = margin as i32;
+                let color = image::Rgb(grid_color.0);
+                imageproc::drawing::draw_filled_rect_mut(
+                    &mut img,
+                    imageproc::rect::Rect::at(offset, margin).of_size(1, length),
+                    color,
+                );
+                imageproc::drawing::draw_filled_rect_mut(
+                    &mut img,
+                    imageproc::rect::Rect::at(margin, offset).of_size(length, 1),
+                    color,
+                );
+            });
+        }
+
+        img
     }
-}
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn terminal_render() {
-        let generator: crate::generator::Seed = "hello".into();
-        let canva = crate::generator::Canva::new(5, generator);
-        let mut buffer = Vec::new();
+    // makes the pixels outside of the mask transparent, with anti-aliased edges
+    fn masked(&self, img: image::RgbImage) -> image::RgbaImage {
+        let image_size = img.width();
 
-        super::Terminal.render(&mut buffer, canva);
+        image::RgbaImage::from_fn(img.width(), img.height(), |x, y| {
+            let image::Rgb([r, g, b]) = *img.get_pixel(x, y);
+            let alpha = self.mask.coverage(x, y, image_size) * 255.0;
 
-        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+            image::Rgba([r, g, b, alpha.round() as u8])
+        })
+    }
 
-        let expect = include_str!("../testdata/terminal_render.ascii");
-        assert_eq!(expect, output)
+    pub fn render<W: std::io::Write + std::io::Seek>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let img = match self.image_size {
+            Some(image_size) => self.sized_image(canva, image_size),
+            None => self.image(canva),
+        };
+
+        match self.mask {
+            Mask::Square => img.write_to(&mut w, image::ImageOutputFormat::Png)?,
+            _ => self
+                .masked(img)
+                .write_to(&mut w, image::ImageOutputFormat::Png)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Scalable vector graphics using the same layout as the PNG output.
+#[derive(Default)]
+pub struct Svg {
+    pub mask: Mask,
+    pub cell_shape: CellShape,
+    /// Space left empty between neighbouring cells, in pixels.
+    pub gap: u32,
+    /// Color of the lines drawn along the border of every cell, if any.
+    pub grid_lines: Option<HexColor>,
+}
+
+// position and width of the square in which the cell at `pt` is drawn, after removing half of the
+// gap on each side
+fn cell_bounds(
+    pt: crate::generator::Point,
+    margin: usize,
+    pixel_size: usize,
+    gap: u32,
+) -> (f32, f32, f32) {
+    let inset = gap as f32 / 2.0;
+    let x = (margin + pt.x * pixel_size) as f32 + inset;
+    let y = (margin + pt.y * pixel_size) as f32 + inset;
+
+    (x, y, (pixel_size as f32 - gap as f32).max(0.0))
+}
+
+impl Svg {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let pixel_size = 50;
+        let margin = pixel_size / 2;
+        let size = canva.size();
+        let image_size = pixel_size * size + margin * 2;
+        let color = hex_color(canva.color());
+
+        let clip_path = self.mask.svg_clip_path(image_size);
+
+        writeln!(
+            w,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\">",
+            size = image_size
+        )?;
+        if let Some(clip_path) = &clip_path {
+            writeln!(w, "<clipPath id=\"mask\">{}</clipPath>", clip_path)?;
+            writeln!(w, "<g clip-path=\"url(#mask)\">")?;
+        }
+        writeln!(
+            w,
+            "<rect width=\"{size}\" height=\"{size}\" fill=\"#ffffff\"/>",
+            size = image_size
+        )?;
+        if self.cell_shape == CellShape::Square {
+            writeln!(w, "<g fill=\"{}\" shape-rendering=\"crispEdges\">", color)?;
+        } else {
+            writeln!(w, "<g fill=\"{}\">", color)?;
+        }
+        canva
+            .into_iter()
+            .filter(|(_, displayed)| *displayed)
+            .try_for_each(|(pt, _)| {
+                let (x, y, width) = cell_bounds(pt, margin, pixel_size, self.gap);
+                let primitive = self.cell_shape.primitive(pt, size, (x, y), width);
+                writeln!(w, "{}", primitive.svg())
+            })?;
+        writeln!(w, "</g>")?;
+        if let Some(grid_color) = self.grid_lines {
+            // lines are shifted by half a pixel to cover the same pixels as in the PNG output
+            let length = pixel_size * size;
+            let path: String = (0..=size)
+                .map(|index| {
+                    let offset = (margin + index * pixel_size) as f32 + 0.5;
+                    format!(
+                        "M{offset} {start}v{length}M{start} {offset}h{length}",
+                        offset = offset,
+                        start = margin,
+                        length = length
+                    )
+                })
+                .collect();
+            writeln!(
+                w,
+                "<path d=\"{}\" stroke=\"{}\" stroke-width=\"1\" fill=\"none\" shape-rendering=\"crispEdges\"/>",
+                path, grid_color
+            )?;
+        }
+        if clip_path.is_some() {
+            writeln!(w, "</g>")?;
+        }
+        writeln!(w, "</svg>")?;
+
+        Ok(())
+    }
+}
+
+/// Windows icon embedding one PNG render of the canva per size.
+pub struct Ico {
+    pub sizes: Vec<u32>,
+}
+
+impl Default for Ico {
+    fn default() -> Self {
+        Self {
+            sizes: vec![16, 32, 48, 256],
+        }
+    }
+}
+
+impl Ico {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        // PNG images embedded in an icon must be RGBA
+        let images: Vec<image::RgbaImage> = self
+            .sizes
+            .iter()
+            .map(|size| Png::default().sized_image(canva.clone(), *size))
+            .map(|img| image::DynamicImage::ImageRgb8(img).into_rgba8())
+            .collect();
+        let frames = images
+            .iter()
+            .map(|img| {
+                image::codecs::ico::IcoFrame::as_png(
+                    img.as_raw(),
+                    img.width(),
+                    img.height(),
+                    image::ColorType::Rgba8,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        image::codecs::ico::IcoEncoder::new(w).encode_images(&frames)?;
+        Ok(())
+    }
+}
+
+/// Set of files needed by a website to use the canva as its favicon.
+pub struct Favicon {
+    /// Name of the application written in the web manifest.
+    pub name: String,
+}
+
+impl Favicon {
+    pub fn render<P: AsRef<std::path::Path>>(
+        &mut self,
+        directory: P,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        use std::io::Write;
+
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+
+        let create = |name: &str| -> Result<std::fs::File, crate::error::Error> {
+            let path = directory.join(name);
+            std::fs::File::create(&path).map_err(|e| {
+                crate::error::Error::from(e).context(format!("cannot open \"{}\"", path.display()))
+            })
+        };
+
+        Ico {
+            sizes: vec![16, 32, 48],
+        }
+        .render(create("favicon.ico")?, canva.clone())?;
+        Svg::default().render(create("favicon.svg")?, canva.clone())?;
+
+        [
+            ("apple-touch-icon.png", 180),
+            ("android-chrome-192x192.png", 192),
+            ("android-chrome-512x512.png", 512),
+        ]
+        .into_iter()
+        .try_for_each(|(name, size)| {
+            let mut file = create(name)?;
+            Png::default()
+                .sized_image(canva.clone(), size)
+                .write_to(&mut file, image::ImageOutputFormat::Png)?;
+            Ok::<(), crate::error::Error>(())
+        })?;
+
+        let manifest = serde_json::json!({
+            "name": self.name,
+            "short_name": self.name,
+            "icons": [
+                {
+                    "src": "/android-chrome-192x192.png",
+                    "sizes": "192x192",
+                    "type": "image/png",
+                },
+                {
+                    "src": "/android-chrome-512x512.png",
+                    "sizes": "512x512",
+                    "type": "image/png",
+                },
+            ],
+            "theme_color": hex_color(canva.color()),
+            "background_color": "#ffffff",
+            "display": "standalone",
+        });
+        let mut file = create("site.webmanifest")?;
+        serde_json::to_writer_pretty(&mut file, &manifest).map_err(std::io::Error::from)?;
+        writeln!(file)?;
+
+        Ok(())
+    }
+}
+
+/// Position of the filled cells of the canva on a page whose origin is the bottom left corner,
+/// as used by print formats. The layout is the same as the PNG output, one unit being one point.
+fn print_layout(canva: crate::generator::Canva) -> (usize, usize, Vec<(usize, usize)>) {
+    let pixel_size = 50;
+    let margin = pixel_size / 2;
+    let page_size = pixel_size * canva.size() + margin * 2;
+
+    let cells = canva
+        .into_iter()
+        .filter(|(_, displayed)| *displayed)
+        .map(|(pt, _)| {
+            (
+                margin + pt.x * pixel_size,
+                page_size - margin - (pt.y + 1) * pixel_size,
+            )
+        })
+        .collect();
+
+    (page_size, pixel_size, cells)
+}
+
+/// Red, green and blue components of a color between 0 and 1, as used by print formats.
+fn print_color(color: crate::generator::Color) -> String {
+    let components: Vec<String> = Png::rgb_color(color)
+        .0
+        .iter()
+        .map(|component| format!("{:.3}", *component as f32 / 255.0))
+        .collect();
+
+    components.join(" ")
+}
+
+/// Single page PDF document drawing each filled cell as a vector rectangle.
+pub struct Pdf;
+
+impl Pdf {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let color = print_color(canva.color());
+        let (page_size, pixel_size, cells) = print_layout(canva);
+
+        let mut content = format!("1 1 1 rg\n0 0 {size} {size} re f\n", size = page_size);
+        content.push_str(&format!("{} rg\n", color));
+        cells.iter().for_each(|(x, y)| {
+            content.push_str(&format!(
+                "{} {} {size} {size} re\n",
+                x,
+                y,
+                size = pixel_size
+            ))
+        });
+        content.push_str("f\n");
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {size} {size}] /Contents 4 0 R /Resources << >> >>",
+                size = page_size
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ),
+        ];
+
+        let mut document = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        objects.iter().enumerate().for_each(|(index, object)| {
+            offsets.push(document.len());
+            document.extend(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).bytes());
+        });
+
+        let xref_offset = document.len();
+        document.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+        offsets
+            .iter()
+            .for_each(|offset| document.extend(format!("{:010} 00000 n \n", offset).bytes()));
+        document.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .bytes(),
+        );
+
+        w.write_all(&document)?;
+        Ok(())
+    }
+}
+
+/// Encapsulated PostScript file drawing each filled cell as a vector rectangle.
+pub struct Eps;
+
+impl Eps {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let color = print_color(canva.color());
+        let (page_size, pixel_size, cells) = print_layout(canva);
+
+        writeln!(w, "%!PS-Adobe-3.0 EPSF-3.0")?;
+        writeln!(w, "%%BoundingBox: 0 0 {size} {size}", size = page_size)?;
+        writeln!(w, "%%Title: pixie")?;
+        writeln!(w, "%%EndComments")?;
+        writeln!(w, "1 1 1 setrgbcolor")?;
+        writeln!(w, "0 0 {size} {size} rectfill", size = page_size)?;
+        writeln!(w, "{} setrgbcolor", color)?;
+        cells.iter().try_for_each(|(x, y)| {
+            writeln!(w, "{} {} {size} {size} rectfill", x, y, size = pixel_size)
+        })?;
+        writeln!(w, "showpage")?;
+        writeln!(w, "%%EOF")?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StlEncoding {
+    Ascii,
+    Binary,
+}
+
+impl std::fmt::Display for StlEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Ascii => "ascii",
+            Self::Binary => "binary",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for StlEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(Self::Ascii),
+            "binary" => Ok(Self::Binary),
+            value => Err(format!("unsupported stl encoding '{}'", value)),
+        }
+    }
+}
+
+/// Stereolithography file of the canva extruded on a base plate.
+pub struct Stl {
+    pub encoding: StlEncoding,
+    pub extrusion: crate::mesh::Extrusion,
+}
+
+impl Stl {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let mesh = crate::mesh::Mesh::extrude(canva, self.extrusion);
+
+        match self.encoding {
+            StlEncoding::Ascii => {
+                writeln!(w, "solid pixie")?;
+                mesh.triangles.iter().try_for_each(|triangle| {
+                    let [nx, ny, nz] = mesh.normal(*triangle);
+                    writeln!(w, "  facet normal {} {} {}", nx, ny, nz)?;
+                    writeln!(w, "    outer loop")?;
+                    triangle.iter().try_for_each(|index| {
+                        let [x, y, z] = mesh.vertices[*index];
+                        writeln!(w, "      vertex {} {} {}", x, y, z)
+                    })?;
+                    writeln!(w, "    endloop")?;
+                    writeln!(w, "  endfacet")
+                })?;
+                writeln!(w, "endsolid pixie")?;
+            }
+            StlEncoding::Binary => {
+                let mut header = [0u8; 80];
+                header[..5].copy_from_slice(b"pixie");
+                w.write_all(&header)?;
+                w.write_all(&(mesh.triangles.len() as u32).to_le_bytes())?;
+                mesh.triangles.iter().try_for_each(|triangle| {
+                    let vertices = triangle.map(|index| mesh.vertices[index]);
+                    std::iter::once(mesh.normal(*triangle))
+                        .chain(vertices)
+                        .flatten()
+                        .try_for_each(|value| w.write_all(&value.to_le_bytes()))?;
+                    // attribute byte count, unused
+                    w.write_all(&[0, 0])
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Wavefront OBJ file of the canva extruded on a base plate.
+pub struct Obj {
+    pub extrusion: crate::mesh::Extrusion,
+}
+
+impl Obj {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let mesh = crate::mesh::Mesh::extrude(canva, self.extrusion);
+
+        writeln!(w, "o pixie")?;
+        mesh.vertices
+            .iter()
+            .try_for_each(|[x, y, z]| writeln!(w, "v {} {} {}", x, y, z))?;
+        // vertex indexes start at 1
+        mesh.triangles
+            .iter()
+            .try_for_each(|[a, b, c]| writeln!(w, "f {} {} {}", a + 1, b + 1, c + 1))?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    CrossStitch,
+    Beads,
+}
+
+/// Printable pattern chart: a numbered grid with one symbol per color, followed by a legend
+/// giving the number of stitches or beads needed for each color.
+pub struct Chart {
+    pub kind: ChartKind,
+}
+
+impl Chart {
+    const FILLED: char = 'X';
+    const EMPTY: char = '.';
+
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let size = canva.size();
+        let color = canva.color();
+        let width = size.to_string().len();
+        let (title, unit) = match self.kind {
+            ChartKind::CrossStitch => ("Cross-stitch pattern", "stitches"),
+            ChartKind::Beads => ("Bead pattern", "beads"),
+        };
+
+        writeln!(w, "{}: {} x {} {}", title, size, size, unit)?;
+        writeln!(w)?;
+
+        let columns: Vec<String> = (1..=size)
+            .map(|column| format!("{:>width$}", column, width = width))
+            .collect();
+        writeln!(w, "{:width$}   {}", "", columns.join(" "), width = width)?;
+        writeln!(
+            w,
+            "{:width$} +{}",
+            "",
+            "-".repeat(size * (width + 1)),
+            width = width
+        )?;
+
+        let mut filled = 0;
+        canva.into_iter().try_for_each(|(pt, shown)| {
+            if pt.x == 0 {
+                write!(w, "{:>width$} |", pt.y + 1, width = width)?;
+            }
+
+            let symbol = if shown {
+                filled += 1;
+                Self::FILLED
+            } else {
+                Self::EMPTY
+            };
+            write!(w, " {:>width$}", symbol, width = width)?;
+
+            if pt.x + 1 == size {
+                writeln!(w)?;
+            }
+
+            Ok::<(), crate::error::Error>(())
+        })?;
+
+        let empty = size * size - filled;
+        let empty_count = match self.kind {
+            ChartKind::CrossStitch => format!("{} (not stitched)", empty),
+            ChartKind::Beads => format!("{} {}", empty, unit),
+        };
+
+        writeln!(w)?;
+        writeln!(w, "Legend:")?;
+        writeln!(
+            w,
+            "  {}  {} {}  {} {}",
+            Self::FILLED,
+            color,
+            hex_color(color),
+            filled,
+            unit
+        )?;
+        writeln!(w, "  {}  background #ffffff  {}", Self::EMPTY, empty_count)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterFormat {
+    Jpeg { quality: u8 },
+    WebP,
+    Bmp,
+    Gif,
+    Tiff,
+}
+
+/// Renders the same image as the PNG output in any other format supported by the image crate.
+pub struct Raster {
+    pub format: RasterFormat,
+}
+
+impl Raster {
+    pub fn render<W: std::io::Write + std::io::Seek>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let img = Png::default().image(canva);
+
+        let format = match self.format {
+            RasterFormat::Jpeg { quality } => image::ImageOutputFormat::Jpeg(quality),
+            RasterFormat::WebP => {
+                image::codecs::webp::WebPEncoder::new_lossless(&mut w).encode(
+                    img.as_raw(),
+                    img.width(),
+                    img.height(),
+                    image::ColorType::Rgb8,
+                )?;
+
+                return Ok(());
+            }
+            RasterFormat::Bmp => image::ImageOutputFormat::Bmp,
+            RasterFormat::Gif => image::ImageOutputFormat::Gif,
+            RasterFormat::Tiff => image::ImageOutputFormat::Tiff,
+        };
+
+        img.write_to(&mut w, format)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetpbmEncoding {
+    Plain,
+    Binary,
+}
+
+impl std::fmt::Display for NetpbmEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Plain => "plain",
+            Self::Binary => "binary",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl std::str::FromStr for NetpbmEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "binary" => Ok(Self::Binary),
+            value => Err(format!("unsupported netpbm encoding '{}'", value)),
+        }
+    }
+}
+
+/// Portable bitmap where each cell of the canva is a single pixel, filled cells being black.
+pub struct Pbm {
+    pub encoding: NetpbmEncoding,
+}
+
+impl Pbm {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let size = canva.size();
+        let cells: Vec<bool> = canva.into_iter().map(|(_, shown)| shown).collect();
+
+        match self.encoding {
+            NetpbmEncoding::Plain => {
+                writeln!(w, "P1\n{} {}", size, size)?;
+                cells.chunks(size.max(1)).try_for_each(|row| {
+                    let row: Vec<&str> = row
+                        .iter()
+                        .map(|shown| if *shown { "1" } else { "0" })
+                        .collect();
+                    writeln!(w, "{}", row.join(" "))
+                })?;
+            }
+            NetpbmEncoding::Binary => {
+                writeln!(w, "P4\n{} {}", size, size)?;
+                cells.chunks(size.max(1)).try_for_each(|row| {
+                    let bytes: Vec<u8> = row
+                        .chunks(8)
+                        .map(|bits| {
+                            bits.iter()
+                                .enumerate()
+                                .filter(|(_, shown)| **shown)
+                                .fold(0u8, |byte, (index, _)| byte | (0x80 >> index))
+                        })
+                        .collect();
+                    w.write_all(&bytes)
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Portable pixmap using the same layout as the PNG output.
+pub struct Ppm {
+    pub encoding: NetpbmEncoding,
+}
+
+impl Ppm {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let img = Png::default().image(canva);
+
+        match self.encoding {
+            NetpbmEncoding::Plain => {
+                writeln!(w, "P3\n{} {}\n255", img.width(), img.height())?;
+                img.pixels()
+                    .try_for_each(|image::Rgb([r, g, b])| writeln!(w, "{} {} {}", r, g, b))?;
+            }
+            NetpbmEncoding::Binary => {
+                writeln!(w, "P6\n{} {}\n255", img.width(), img.height())?;
+                w.write_all(img.as_raw())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// X PixMap using the same layout as the PNG output.
+pub struct Xpm;
+
+impl Xpm {
+    pub fn render<W: std::io::Write>(
+        &mut self,
+        mut w: W,
+        canva: crate::generator::Canva,
+    ) -> Result<(), crate::error::Error> {
+        let color = hex_color(canva.color());
+        let img = Png::default().image(canva);
+        let background = image::Rgb([255, 255, 255]);
+
+        writeln!(w, "/* XPM */")?;
+        writeln!(w, "static char *pixie[] = {{")?;
+        writeln!(w, "\"{} {} 2 1\",", img.width(), img.height())?;
+        writeln!(w, "\"  c #ffffff\",")?;
+        writeln!(w, "\". c {}\",", color)?;
+        img.rows().enumerate().try_for_each(|(index, row)| {
+            let row: String = row
+                .map(|pixel| if *pixel == background { ' ' } else { '.' })
+                .collect();
+            let separator = if index + 1 == img.height() as usize {
+                ""
+            } else {
+                ","
+            };
+            writeln!(w, "\"{}\"{}", row, separator)
+        })?;
+        writeln!(w, "}};")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn terminal_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Terminal::default().render(&mut buffer, canva);
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/terminal_render.ascii");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn color_depth_detect_colorterm_truecolor() {
+        let depth = super::ColorDepth::detect(Some("truecolor"), Some("xterm-256color"));
+
+        assert_eq!(super::ColorDepth::TrueColor, depth)
+    }
+
+    #[test]
+    fn color_depth_detect_colorterm_24bit() {
+        let depth = super::ColorDepth::detect(Some("24bit"), None);
+
+        assert_eq!(super::ColorDepth::TrueColor, depth)
+    }
+
+    #[test]
+    fn color_depth_detect_term_direct() {
+        let depth = super::ColorDepth::detect(None, Some("xterm-direct"));
+
+        assert_eq!(super::ColorDepth::TrueColor, depth)
+    }
+
+    #[test]
+    fn color_depth_detect_term_256color() {
+        let depth = super::ColorDepth::detect(None, Some("screen-256color"));
+
+        assert_eq!(super::ColorDepth::Ansi256, depth)
+    }
+
+    #[test]
+    fn color_depth_detect_term_basic() {
+        let depth = super::ColorDepth::detect(None, Some("linux"));
+
+        assert_eq!(super::ColorDepth::Ansi16, depth)
+    }
+
+    #[test]
+    fn color_depth_detect_nothing() {
+        let depth = super::ColorDepth::detect(None, None);
+
+        assert_eq!(super::ColorDepth::Ansi256, depth)
+    }
+
+    #[test]
+    fn color_depth_from_string() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            Ok(super::ColorDepth::Ansi16),
+            super::ColorDepth::from_str("16")
+        );
+        assert_eq!(
+            Ok(super::ColorDepth::Ansi256),
+            super::ColorDepth::from_str("256")
+        );
+        assert_eq!(
+            Ok(super::ColorDepth::TrueColor),
+            super::ColorDepth::from_str("truecolor")
+        );
+        assert_eq!(
+            Err("unsupported color depth 'nope'".to_string()),
+            super::ColorDepth::from_str("nope")
+        );
+    }
+
+    #[test]
+    fn color_depth_display() {
+        assert_eq!("16", format!("{}", super::ColorDepth::Ansi16));
+        assert_eq!("256", format!("{}", super::ColorDepth::Ansi256));
+        assert_eq!("truecolor", format!("{}", super::ColorDepth::TrueColor));
+    }
+
+    #[test]
+    fn terminal_foreground_truecolor_matches_png() {
+        let terminal = super::Terminal {
+            color_depth: super::ColorDepth::TrueColor,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "38;2;222;48;48",
+            terminal.foreground(crate::generator::Color::Red)
+        );
+        assert_eq!(
+            "38;2;48;146;227",
+            terminal.foreground(crate::generator::Color::Blue)
+        );
+    }
+
+    #[test]
+    fn terminal_foreground_ansi_16() {
+        let terminal = super::Terminal {
+            color_depth: super::ColorDepth::Ansi16,
+            ..Default::default()
+        };
+
+        assert_eq!("31", terminal.foreground(crate::generator::Color::Red));
+        assert_eq!("94", terminal.foreground(crate::generator::Color::Blue));
+        assert_eq!("30", terminal.foreground(crate::generator::Color::Black));
+        assert_eq!("93", terminal.foreground(crate::generator::Color::Yellow));
+    }
+
+    #[test]
+    fn terminal_render_truecolor() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Terminal {
+            color_depth: super::ColorDepth::TrueColor,
+            ..Default::default()
+        }
+        .render(&mut buffer, canva);
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/terminal_render.ascii")
+            .replace("38;5;232;48;5;15", "38;2;0;0;0;48;2;255;255;255");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn border_from_string() {
+        use std::str::FromStr;
+
+        assert_eq!(Ok(super::Border::None), super::Border::from_str("none"));
+        assert_eq!(Ok(super::Border::Single), super::Border::from_str("single"));
+        assert_eq!(Ok(super::Border::Double), super::Border::from_str("double"));
+        assert_eq!(
+            Ok(super::Border::Rounded),
+            super::Border::from_str("rounded")
+        );
+        assert_eq!(Ok(super::Border::Heavy), super::Border::from_str("heavy"));
+        assert_eq!(
+            Err("unsupported border style 'nope'".to_string()),
+            super::Border::from_str("nope")
+        );
+    }
+
+    #[test]
+    fn border_display() {
+        assert_eq!("none", format!("{}", super::Border::None));
+        assert_eq!("single", format!("{}", super::Border::Single));
+        assert_eq!("double", format!("{}", super::Border::Double));
+        assert_eq!("rounded", format!("{}", super::Border::Rounded));
+        assert_eq!("heavy", format!("{}", super::Border::Heavy));
+    }
+
+    #[test]
+    fn terminal_render_without_frame() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(2, generator);
+        let mut buffer = Vec::new();
+
+        super::Terminal {
+            color_depth: super::ColorDepth::Ansi16,
+            border: super::Border::None,
+            padding: 0,
+            background: false,
+        }
+        .render(&mut buffer, canva);
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = "\x1b[30m████\x1b[0m\n\x1b[30m████\x1b[0m\n";
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn terminal_render_rounded_with_padding() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(2, generator);
+        let mut buffer = Vec::new();
+
+        super::Terminal {
+            color_depth: super::ColorDepth::Ansi16,
+            border: super::Border::Rounded,
+            padding: 2,
+            background: true,
+        }
+        .render(&mut buffer, canva);
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = [
+            "\x1b[30;107m╭────────╮\x1b[0m",
+            "\x1b[30;107m│        │\x1b[0m",
+            "\x1b[30;107m│  ████  │\x1b[0m",
+            "\x1b[30;107m│  ████  │\x1b[0m",
+            "\x1b[30;107m│        │\x1b[0m",
+            "\x1b[30;107m╰────────╯\x1b[0m",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn emoji_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Emoji
+            .render(&mut buffer, canva)
+            .expect("failed to render emoji");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/emoji_render.txt");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn emoji_square() {
+        let colors = [
+            crate::generator::Color::Black,
+            crate::generator::Color::Blue,
+            crate::generator::Color::Brown,
+            crate::generator::Color::Green,
+            crate::generator::Color::Pink,
+            crate::generator::Color::Purple,
+            crate::generator::Color::Red,
+            crate::generator::Color::Yellow,
+        ];
+
+        let squares: String = colors.map(super::Emoji::square).iter().collect();
+        assert_eq!("⬛🟦🟫🟩🟪🟪🟥🟨", squares);
+    }
+
+    #[test]
+    fn html_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Html::default()
+            .render(&mut buffer, canva)
+            .expect("failed to render HTML");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/html_render.html");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn css_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Css::default()
+            .render(&mut buffer, canva)
+            .expect("failed to render CSS");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/css_render.css");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn json_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let hash = generator.hash();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Json { hash }
+            .render(&mut buffer, canva)
+            .expect("failed to render JSON");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/json_render.json");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn svg_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Svg::default()
+            .render(&mut buffer, canva)
+            .expect("failed to render SVG");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/svg_render.svg");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn mask_contains() {
+        assert!(super::Mask::Square.contains(-1.0, 1.0));
+        assert!(!super::Mask::Square.contains(-1.1, 0.0));
+
+        assert!(super::Mask::Circle.contains(0.0, 1.0));
+        assert!(!super::Mask::Circle.contains(0.9, 0.9));
+
+        let rounded = super::Mask::Rounded { radius: 0.5 };
+        assert!(rounded.contains(1.0, 0.5));
+        assert!(rounded.contains(0.8, 0.8));
+        assert!(!rounded.contains(0.95, 0.95));
+
+        assert!(super::Mask::Squircle.contains(0.8, 0.8));
+        assert!(!super::Mask::Squircle.contains(0.9, 0.9));
+    }
+
+    #[test]
+    fn mask_coverage() {
+        assert_eq!(1.0, super::Mask::Circle.coverage(50, 50, 100));
+        assert_eq!(0.0, super::Mask::Circle.coverage(0, 0, 100));
+
+        let edge = super::Mask::Circle.coverage(14, 14, 100);
+        assert!(edge > 0.0 && edge < 1.0);
+    }
+
+    #[test]
+    fn png_render_circle_mask() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Png {
+            mask: super::Mask::Circle,
+            ..Default::default()
+        }
+        .render(std::io::Cursor::new(&mut buffer), canva)
+        .expect("failed to render PNG");
+
+        let img = image::load_from_memory(&buffer)
+            .expect("failed to decode PNG")
+            .into_rgba8();
+
+        assert_eq!(image::Rgba([255, 255, 255, 0]), *img.get_pixel(0, 0));
+        assert_eq!(image::Rgba([255, 255, 255, 0]), *img.get_pixel(299, 299));
+        assert_eq!(image::Rgba([255, 255, 255, 255]), *img.get_pixel(150, 150));
+        assert_eq!(image::Rgba([0, 0, 0, 255]), *img.get_pixel(100, 40));
+
+        let image::Rgba([_, _, _, alpha]) = *img.get_pixel(100, 8);
+        assert!(alpha > 0 && alpha < 255);
+    }
+
+    #[test]
+    fn svg_render_masks() {
+        let render = |mask| {
+            let generator: crate::generator::Seed = "hello".into();
+            let canva = crate::generator::Canva::new(5, generator);
+            let mut buffer = Vec::new();
+
+            super::Svg {
+                mask,
+                ..Default::default()
+            }
+            .render(&mut buffer, canva)
+            .expect("failed to render SVG");
+
+            String::from_utf8(buffer).expect("failed to cast bytes to string")
+        };
+
+        let circle = render(super::Mask::Circle);
+        let rounded = render(super::Mask::Rounded { radius: 0.5 });
+        let squircle = render(super::Mask::Squircle);
+
+        assert!(circle.contains(
+            "<clipPath id=\"mask\"><circle cx=\"150\" cy=\"150\" r=\"150\"/></clipPath>\n<g clip-path=\"url(#mask)\">\n<rect width=\"300\" height=\"300\" fill=\"#ffffff\"/>"
+        ));
+        assert!(circle.ends_with("</g>\n</g>\n</svg>\n"));
+        assert!(rounded.contains(
+            "<clipPath id=\"mask\"><rect width=\"300\" height=\"300\" rx=\"75\" ry=\"75\"/></clipPath>"
+        ));
+        assert!(squircle.contains("<clipPath id=\"mask\"><polygon points=\"300.00,150.00 "));
+    }
+
+    #[test]
+    fn cell_shape_from_string() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            Ok(super::CellShape::Square),
+            super::CellShape::from_str("square")
+        );
+        assert_eq!(Ok(super::CellShape::Dot), super::CellShape::from_str("dot"));
+        assert_eq!(
+            Ok(super::CellShape::Diamond),
+            super::CellShape::from_str("diamond")
+        );
+        assert_eq!(
+            Ok(super::CellShape::Rounded),
+            super::CellShape::from_str("rounded")
+        );
+        assert_eq!(
+            Ok(super::CellShape::Triangle),
+            super::CellShape::from_str("triangle")
+        );
+        assert_eq!(
+            Err("unsupported cell shape 'nope'".to_string()),
+            super::CellShape::from_str("nope")
+        );
+    }
+
+    #[test]
+    fn cell_shape_display() {
+        assert_eq!("square", format!("{}", super::CellShape::Square));
+        assert_eq!("dot", format!("{}", super::CellShape::Dot));
+        assert_eq!("diamond", format!("{}", super::CellShape::Diamond));
+        assert_eq!("rounded", format!("{}", super::CellShape::Rounded));
+        assert_eq!("triangle", format!("{}", super::CellShape::Triangle));
+    }
+
+    #[test]
+    fn cell_shape_triangle_orientation() {
+        let triangle = |x, y| {
+            super::CellShape::Triangle.primitive(
+                crate::generator::Point { x, y },
+                5,
+                (0.0, 0.0),
+                10.0,
+            )
+        };
+
+        assert_eq!(
+            super::Primitive::Polygon(vec![(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]),
+            triangle(0, 1)
+        );
+        assert_eq!(
+            super::Primitive::Polygon(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]),
+            triangle(4, 1)
+        );
+        assert_eq!(
+            super::Primitive::Polygon(vec![(0.0, 0.0), (10.0, 0.0), (5.0, 10.0)]),
+            triangle(2, 0)
+        );
+        assert_eq!(
+            super::Primitive::Polygon(vec![(10.0, 0.0), (10.0, 10.0), (0.0, 5.0)]),
+            triangle(3, 2)
+        );
+        assert_eq!(
+            super::Primitive::Polygon(vec![(5.0, 0.0), (10.0, 5.0), (5.0, 10.0), (0.0, 5.0)]),
+            triangle(2, 2)
+        );
+    }
+
+    #[test]
+    fn png_render_cell_shapes() {
+        let render = |cell_shape| {
+            let generator: crate::generator::Seed = "hello".into();
+            let canva = crate::generator::Canva::new(5, generator);
+
+            super::Png {
+                cell_shape,
+                ..Default::default()
+            }
+            .image(canva)
+        };
+
+        let dot = render(super::CellShape::Dot);
+        assert_eq!(image::Rgb([255, 255, 255]), *dot.get_pixel(26, 26));
+        assert_eq!(image::Rgb([0, 0, 0]), *dot.get_pixel(50, 50));
+        assert_eq!(image::Rgb([0, 0, 0]), *dot.get_pixel(26, 50));
+
+        let diamond = render(super::CellShape::Diamond);
+        assert_eq!(image::Rgb([255, 255, 255]), *diamond.get_pixel(30, 30));
+        assert_eq!(image::Rgb([0, 0, 0]), *diamond.get_pixel(50, 50));
+
+        let rounded = render(super::CellShape::Rounded);
+        assert_eq!(image::Rgb([255, 255, 255]), *rounded.get_pixel(25, 25));
+        assert_eq!(image::Rgb([0, 0, 0]), *rounded.get_pixel(26, 50));
+        assert_eq!(image::Rgb([0, 0, 0]), *rounded.get_pixel(50, 25));
+
+        let triangle = render(super::CellShape::Triangle);
+        assert_eq!(image::Rgb([0, 0, 0]), *triangle.get_pixel(30, 30));
+        assert_eq!(image::Rgb([255, 255, 255]), *triangle.get_pixel(70, 70));
+        assert_eq!(image::Rgb([0, 0, 0]), *triangle.get_pixel(270, 30));
+        assert_eq!(image::Rgb([255, 255, 255]), *triangle.get_pixel(230, 70));
+    }
+
+    #[test]
+    fn svg_render_cell_shape() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Svg {
+            cell_shape: super::CellShape::Dot,
+            ..Default::default()
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render SVG");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        assert!(output.contains("<g fill=\"#000000\">\n<circle cx=\"50\" cy=\"50\" r=\"25\"/>\n"));
+    }
+
+    #[test]
+    fn hex_color_from_string() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            Ok(super::HexColor([224, 16, 255])),
+            super::HexColor::from_str("#e010ff")
+        );
+        assert_eq!(
+            Ok(super::HexColor([224, 16, 255])),
+            super::HexColor::from_str("#E010FF")
+        );
+        assert_eq!(
+            Err("unsupported color 'e010ff'".to_string()),
+            super::HexColor::from_str("e010ff")
+        );
+        assert_eq!(
+            Err("unsupported color '#e010f'".to_string()),
+            super::HexColor::from_str("#e010f")
+        );
+        assert_eq!(
+            Err("unsupported color '#e010fg'".to_string()),
+            super::HexColor::from_str("#e010fg")
+        );
+    }
+
+    #[test]
+    fn hex_color_display() {
+        assert_eq!("#e010ff", format!("{}", super::HexColor([224, 16, 255])));
+    }
+
+    #[test]
+    fn png_render_gap_and_grid_lines() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+
+        let img = super::Png {
+            gap: 6,
+            grid_lines: Some(super::HexColor([255, 0, 255])),
+            ..Default::default()
+        }
+        .image(canva);
+
+        // gap between the border of the cell and the filled area
+        assert_eq!(image::Rgb([255, 255, 255]), *img.get_pixel(27, 50));
+        assert_eq!(image::Rgb([0, 0, 0]), *img.get_pixel(28, 50));
+        assert_eq!(image::Rgb([0, 0, 0]), *img.get_pixel(71, 50));
+        assert_eq!(image::Rgb([255, 255, 255]), *img.get_pixel(72, 50));
+        // grid lines on both axes, including around the empty cells
+        assert_eq!(image::Rgb([255, 0, 255]), *img.get_pixel(25, 50));
+        assert_eq!(image::Rgb([255, 0, 255]), *img.get_pixel(50, 75));
+        assert_eq!(image::Rgb([255, 0, 255]), *img.get_pixel(275, 260));
+        assert_eq!(image::Rgb([255, 255, 255]), *img.get_pixel(276, 260));
+    }
+
+    #[test]
+    fn svg_render_gap_and_grid_lines() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Svg {
+            gap: 6,
+            grid_lines: Some(super::HexColor([255, 0, 255])),
+            ..Default::default()
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render SVG");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        assert!(output.contains("<rect x=\"28\" y=\"28\" width=\"44\" height=\"44\"/>\n"));
+        assert!(output
+            .contains("<path d=\"M25.5 25v250M25 25.5h250M75.5 25v250M25 75.5h250M125.5 25v250"));
+        assert!(output.contains("M275.5 25v250M25 275.5h250\" stroke=\"#ff00ff\""));
+    }
+
+    #[test]
+    fn ico_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Ico::default()
+            .render(&mut buffer, canva)
+            .expect("failed to render ICO");
+
+        let decoder = image::codecs::ico::IcoDecoder::new(std::io::Cursor::new(&buffer))
+            .expect("failed to decode ICO");
+        let largest = image::DynamicImage::from_decoder(decoder).expect("failed to read ICO");
+
+        // header of the ICO file: reserved field, image type and number of images
+        assert_eq!([0, 0, 1, 0, 4, 0], buffer[..6]);
+        assert_eq!(
+            [16, 32, 48, 0],
+            [buffer[6], buffer[6 + 16], buffer[6 + 32], buffer[6 + 48]]
+        );
+        assert_eq!((256, 256), (largest.width(), largest.height()));
+    }
+
+    #[test]
+    fn png_sized_image() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+
+        let img = super::Png::default().sized_image(canva, 48);
+
+        assert_eq!((48, 48), (img.width(), img.height()));
+        assert_eq!(image::Rgb([255, 255, 255]), *img.get_pixel(1, 1));
+        assert_eq!(image::Rgb([0, 0, 0]), *img.get_pixel(7, 7));
+    }
+
+    #[test]
+    fn png_sized_image_uniform_cells() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let filled = canva
+            .clone()
+            .into_iter()
+            .filter(|(_, displayed)| *displayed)
+            .count();
+
+        // cells are 100 / 6 pixels wide
+        let img = super::Png::default().sized_image(canva, 100);
+
+        let coverage: f32 = img
+            .pixels()
+            .map(|image::Rgb([r, _, _])| (255 - *r) as f32 / 255.0)
+            .sum();
+        let expect = filled as f32 * (100.0f32 / 6.0).powi(2);
+        assert!((expect - coverage).abs() / expect < 0.01);
+
+        // the first cell starts a third of a pixel before the end of the pixel 8
+        let image::Rgb([edge, _, _]) = *img.get_pixel(8, 12);
+        assert!(edge > 0 && edge < 255);
+        assert_eq!(image::Rgb([255, 255, 255]), *img.get_pixel(7, 12));
+        assert_eq!(image::Rgb([0, 0, 0]), *img.get_pixel(9, 12));
+    }
+
+    #[test]
+    fn png_render_image_size() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = std::io::Cursor::new(Vec::new());
+
+        super::Png {
+            image_size: Some(123),
+            ..Default::default()
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render PNG");
+
+        let img = image::load_from_memory(buffer.get_ref()).expect("failed to decode PNG");
+        assert_eq!((123, 123), (img.width(), img.height()));
+    }
+
+    #[test]
+    fn favicon_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let directory = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+
+        super::Favicon {
+            name: "hello".to_string(),
+        }
+        .render(&directory, canva)
+        .expect("failed to render favicon");
+
+        let mut files: Vec<String> = std::fs::read_dir(&directory)
+            .expect("failed to list favicon directory")
+            .map(|entry| {
+                entry
+                    .expect("failed to read directory entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        files.sort();
+
+        let apple_touch_icon = image::open(directory.join("apple-touch-icon.png"))
+            .expect("failed to read apple touch icon");
+        let manifest = std::fs::read_to_string(directory.join("site.webmanifest"))
+            .expect("failed to read web manifest");
+        let manifest: serde_json::Value =
+            serde_json::from_str(&manifest).expect("failed to parse web manifest");
+
+        std::fs::remove_dir_all(&directory).expect("failed to remove favicon directory");
+
+        assert_eq!(
+            vec![
+                "android-chrome-192x192.png",
+                "android-chrome-512x512.png",
+                "apple-touch-icon.png",
+                "favicon.ico",
+                "favicon.svg",
+                "site.webmanifest"
+            ],
+            files
+        );
+        assert_eq!(
+            (180, 180),
+            (apple_touch_icon.width(), apple_touch_icon.height())
+        );
+        assert_eq!("hello", manifest["name"]);
+        assert_eq!("#000000", manifest["theme_color"]);
+        assert_eq!("/android-chrome-512x512.png", manifest["icons"][1]["src"]);
+    }
+
+    #[test]
+    fn print_color() {
+        assert_eq!(
+            "0.871 0.188 0.188",
+            super::print_color(crate::generator::Color::Red)
+        );
+        assert_eq!(
+            "0.000 0.000 0.000",
+            super::print_color(crate::generator::Color::Black)
+        );
+    }
+
+    #[test]
+    fn pdf_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Pdf
+            .render(&mut buffer, canva)
+            .expect("failed to render PDF");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/pdf_render.pdf");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn eps_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Eps
+            .render(&mut buffer, canva)
+            .expect("failed to render EPS");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/eps_render.eps");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn stl_encoding_from_string() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            Ok(super::StlEncoding::Ascii),
+            super::StlEncoding::from_str("ascii")
+        );
+        assert_eq!(
+            Ok(super::StlEncoding::Binary),
+            super::StlEncoding::from_str("binary")
+        );
+        assert_eq!(
+            Err("unsupported stl encoding 'nope'".to_string()),
+            super::StlEncoding::from_str("nope")
+        );
+    }
+
+    #[test]
+    fn stl_encoding_display() {
+        assert_eq!("ascii", format!("{}", super::StlEncoding::Ascii));
+        assert_eq!("binary", format!("{}", super::StlEncoding::Binary));
+    }
+
+    #[test]
+    fn stl_render_ascii() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(2, generator);
+        let mut buffer = Vec::new();
+
+        super::Stl {
+            encoding: super::StlEncoding::Ascii,
+            extrusion: crate::mesh::Extrusion::default(),
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render STL");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(2 + 48 * 7, lines.len());
+        assert_eq!("solid pixie", lines[0]);
+        assert_eq!("  facet normal 0 0 1", lines[1]);
+        assert_eq!("    outer loop", lines[2]);
+        assert_eq!("      vertex 0 5 3", lines[3]);
+        assert_eq!("      vertex 5 5 3", lines[4]);
+        assert_eq!("      vertex 5 10 3", lines[5]);
+        assert_eq!("    endloop", lines[6]);
+        assert_eq!("  endfacet", lines[7]);
+        assert_eq!("endsolid pixie", lines[lines.len() - 1]);
+    }
+
+    #[test]
+    fn stl_render_binary() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(2, generator);
+        let mut buffer = Vec::new();
+
+        super::Stl {
+            encoding: super::StlEncoding::Binary,
+            extrusion: crate::mesh::Extrusion::default(),
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render STL");
+
+        let float = |offset: usize| {
+            f32::from_le_bytes(
+                buffer[offset..offset + 4]
+                    .try_into()
+                    .expect("invalid float"),
+            )
+        };
+
+        assert_eq!(80 + 4 + 48 * 50, buffer.len());
+        assert_eq!(b"pixie", &buffer[..5]);
+        assert_eq!(48u32.to_le_bytes(), buffer[80..84]);
+        assert_eq!([0.0, 0.0, 1.0], [float(84), float(88), float(92)]);
+        assert_eq!([0.0, 5.0, 3.0], [float(96), float(100), float(104)]);
+        assert_eq!([5.0, 5.0, 3.0], [float(108), float(112), float(116)]);
+    }
+
+    #[test]
+    fn obj_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(2, generator);
+        let mut buffer = Vec::new();
+
+        super::Obj {
+            extrusion: crate::mesh::Extrusion::default(),
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render OBJ");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(1 + 26 + 48, lines.len());
+        assert_eq!("o pixie", lines[0]);
+        assert_eq!("v 0 5 3", lines[1]);
+        assert_eq!("v 5 5 3", lines[2]);
+        assert_eq!("f 1 2 3", lines[27]);
+        assert_eq!("f 1 3 4", lines[28]);
+    }
+
+    #[test]
+    fn chart_render_cross_stitch() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Chart {
+            kind: super::ChartKind::CrossStitch,
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render chart");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = include_str!("../testdata/chart_render.txt");
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn chart_render_beads() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(12, generator);
+        let mut buffer = Vec::new();
+
+        super::Chart {
+            kind: super::ChartKind::Beads,
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render chart");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!("Bead pattern: 12 x 12 beads", lines[0]);
+        assert_eq!("      1  2  3  4  5  6  7  8  9 10 11 12", lines[2]);
+        assert_eq!(format!("   +{}", "-".repeat(36)), lines[3]);
+        assert!(lines[4].starts_with(" 1 |  "));
+        assert!(lines[15].starts_with("12 |  "));
+        assert_eq!("Legend:", lines[17]);
+        assert!(lines[19].ends_with(" beads"));
+    }
+
+    fn raster_render(format: super::RasterFormat) -> image::DynamicImage {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Raster { format }
+            .render(std::io::Cursor::new(&mut buffer), canva)
+            .expect("failed to render image");
+
+        image::load_from_memory(&buffer).expect("failed to decode rendered image")
+    }
+
+    #[test]
+    fn raster_render_jpeg() {
+        let actual = raster_render(super::RasterFormat::Jpeg { quality: 90 });
+
+        assert_eq!((300, 300), (actual.width(), actual.height()));
+    }
+
+    #[test]
+    fn raster_render_webp() {
+        let actual = raster_render(super::RasterFormat::WebP);
+
+        let expect = image::open("testdata/png_render.png").expect("failed to read expected PNG");
+        assert_eq!(expect.into_rgb8(), actual.into_rgb8());
+    }
+
+    #[test]
+    fn raster_render_bmp() {
+        let actual = raster_render(super::RasterFormat::Bmp);
+
+        let expect = image::open("testdata/png_render.png").expect("failed to read expected PNG");
+        assert_eq!(expect.into_rgb8(), actual.into_rgb8());
+    }
+
+    #[test]
+    fn raster_render_gif() {
+        let actual = raster_render(super::RasterFormat::Gif);
+
+        assert_eq!((300, 300), (actual.width(), actual.height()));
+    }
+
+    #[test]
+    fn raster_render_tiff() {
+        let actual = raster_render(super::RasterFormat::Tiff);
+
+        let expect = image::open("testdata/png_render.png").expect("failed to read expected PNG");
+        assert_eq!(expect.into_rgb8(), actual.into_rgb8());
+    }
+
+    #[test]
+    fn netpbm_encoding_from_string() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            Ok(super::NetpbmEncoding::Plain),
+            super::NetpbmEncoding::from_str("plain")
+        );
+        assert_eq!(
+            Ok(super::NetpbmEncoding::Binary),
+            super::NetpbmEncoding::from_str("binary")
+        );
+        assert_eq!(
+            Err("unsupported netpbm encoding 'nope'".to_string()),
+            super::NetpbmEncoding::from_str("nope")
+        );
+    }
+
+    #[test]
+    fn netpbm_encoding_display() {
+        assert_eq!("plain", format!("{}", super::NetpbmEncoding::Plain));
+        assert_eq!("binary", format!("{}", super::NetpbmEncoding::Binary));
+    }
+
+    #[test]
+    fn pbm_render_plain() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Pbm {
+            encoding: super::NetpbmEncoding::Plain,
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render PBM");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+
+        let expect = "P1\n5 5\n1 1 0 1 1\n0 1 0 1 0\n0 1 0 1 0\n0 1 0 1 0\n1 1 0 1 1\n";
+        assert_eq!(expect, output)
+    }
+
+    #[test]
+    fn pbm_render_binary() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Pbm {
+            encoding: super::NetpbmEncoding::Binary,
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render PBM");
+
+        let mut expect = b"P4\n5 5\n".to_vec();
+        expect.extend([0b11011000, 0b01010000, 0b01010000, 0b01010000, 0b11011000]);
+        assert_eq!(expect, buffer)
+    }
+
+    #[test]
+    fn ppm_render_binary_matches_png() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Ppm {
+            encoding: super::NetpbmEncoding::Binary,
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render PPM");
+
+        let expect = image::open("testdata/png_render.png")
+            .expect("failed to read expected PNG")
+            .into_rgb8();
+        let actual = image::load_from_memory_with_format(&buffer, image::ImageFormat::Pnm)
+            .expect("failed to decode PPM")
+            .into_rgb8();
+
+        assert!(buffer.starts_with(b"P6\n300 300\n255\n"));
+        assert_eq!(expect, actual)
+    }
+
+    #[test]
+    fn ppm_render_plain_matches_png() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Ppm {
+            encoding: super::NetpbmEncoding::Plain,
+        }
+        .render(&mut buffer, canva)
+        .expect("failed to render PPM");
+
+        let expect = image::open("testdata/png_render.png")
+            .expect("failed to read expected PNG")
+            .into_rgb8();
+        let actual = image::load_from_memory_with_format(&buffer, image::ImageFormat::Pnm)
+            .expect("failed to decode PPM")
+            .into_rgb8();
+
+        assert!(buffer.starts_with(b"P3\n300 300\n255\n255 255 255\n"));
+        assert_eq!(expect, actual)
+    }
+
+    #[test]
+    fn xpm_render() {
+        let generator: crate::generator::Seed = "hello".into();
+        let canva = crate::generator::Canva::new(5, generator);
+        let mut buffer = Vec::new();
+
+        super::Xpm
+            .render(&mut buffer, canva)
+            .expect("failed to render XPM");
+
+        let output = String::from_utf8(buffer).expect("failed to cast bytes to string");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(306, lines.len());
+        assert_eq!("/* XPM */", lines[0]);
+        assert_eq!("static char *pixie[] = {", lines[1]);
+        assert_eq!("\"300 300 2 1\",", lines[2]);
+        assert_eq!("\"  c #ffffff\",", lines[3]);
+        assert_eq!("\". c #000000\",", lines[4]);
+        assert_eq!(format!("\"{}\",", " ".repeat(300)), lines[5]);
+        assert_eq!(
+            format!(
+                "\"{}{}{}{}{}\",",
+                " ".repeat(25),
+                ".".repeat(100),
+                " ".repeat(50),
+                ".".repeat(100),
+                " ".repeat(25)
+            ),
+            lines[30]
+        );
+        assert_eq!(format!("\"{}\"", " ".repeat(300)), lines[304]);
+        assert_eq!("};", lines[305]);
     }
 
     #[test]
@@ -110,7 +2822,7 @@ mod tests {
         let canva = crate::generator::Canva::new(5, generator);
         let mut buffer = Vec::new();
 
-        super::Png
+        super::Png::default()
             .render(std::io::Cursor::new(&mut buffer), canva)
             .expect("failed to render PNG");
 
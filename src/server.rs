@@ -0,0 +1,898 @@
+use sha2::Digest;
+
+// limits protecting the server from requests which would take too long to generate
+const MAX_SIZE: usize = 64;
+const MAX_PX: u32 = 2048;
+const MAX_HEADERS_LENGTH: usize = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Reads the request line and the headers of an HTTP/1.x request.
+    pub fn read<R: std::io::BufRead>(mut r: R) -> Result<Self, crate::error::Error> {
+        let invalid = || crate::error::Error::generic("malformed request".to_string());
+        let mut length = 0;
+        let mut line = || -> Result<String, crate::error::Error> {
+            let mut line = String::new();
+            let read = std::io::BufRead::read_line(
+                &mut std::io::Read::take(&mut r, (MAX_HEADERS_LENGTH - length) as u64),
+                &mut line,
+            )?;
+            length += read;
+            if !line.ends_with('\n') {
+                return Err(invalid());
+            }
+
+            Ok(line.trim_end_matches(['\r', '\n']).to_string())
+        };
+
+        let request_line = line()?;
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(target), Some(version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if !version.starts_with("HTTP/1.") || !target.starts_with('/') {
+            return Err(invalid());
+        }
+
+        let mut headers = Vec::new();
+        loop {
+            let header = line()?;
+            if header.is_empty() {
+                break;
+            }
+
+            let (name, value) = header.split_once(':').ok_or_else(invalid)?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        Ok(Self {
+            method: method.to_string(),
+            target: target.to_string(),
+            headers,
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn text(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type", "text/plain; charset=utf-8".to_string())],
+            body: format!("{}\n", message).into_bytes(),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            304 => "Not Modified",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Writes the response, without its body when answering a HEAD request. Connections are not
+    /// kept alive.
+    pub fn write<W: std::io::Write>(&self, mut w: W, with_body: bool) -> std::io::Result<()> {
+        write!(w, "HTTP/1.1 {} {}\r\n", self.status, self.reason())?;
+        for (name, value) in &self.headers {
+            write!(w, "{}: {}\r\n", name, value)?;
+        }
+        if self.status != 304 {
+            write!(w, "Content-Length: {}\r\n", self.body.len())?;
+        }
+        write!(w, "Connection: close\r\n\r\n")?;
+        if with_body {
+            w.write_all(&self.body)?;
+        }
+
+        w.flush()
+    }
+}
+
+// decodes the %XX sequences of a URL component, and '+' as a space in query strings
+fn percent_decode(value: &str, plus_as_space: bool) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let hex = std::str::from_utf8(bytes.get(index + 1..index + 3)?).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            }
+            b'+' if plus_as_space => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Png,
+    Svg,
+}
+
+/// Settings of the server which change the generated images.
+#[derive(Clone, Default)]
+pub struct Config {
+    /// Hash function turning the words into seeds.
+    pub hash: crate::generator::HashFunction,
+    /// Secret turning the hashing of the words and of the avatar hashes into their keyed
+    /// variant.
+    pub key: Option<Vec<u8>>,
+    /// Normalizations applied in order to the words before they are hashed.
+    pub normalize: Vec<crate::normalization::Normalization>,
+}
+
+// image requested by a client
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Image {
+    seed: crate::generator::Seed,
+    format: Format,
+    size: usize,
+    px: Option<u32>,
+}
+
+// parses a query parameter which must be a number within the given bounds
+fn bounded<T>(name: &str, value: &str, max: T) -> Result<T, Response>
+where
+    T: std::str::FromStr + PartialOrd + From<u8> + std::fmt::Display + Copy,
+{
+    value
+        .parse()
+        .ok()
+        .filter(|value| (T::from(1)..=max).contains(value))
+        .ok_or_else(|| Response::text(400, &format!("{} must be between 1 and {}", name, max)))
+}
+
+// decodes the lowercase or uppercase hex digest of a Gravatar URL, which is a md5, a sha256 or
+// blake3, or a sha512
+fn hex_digest(value: &str) -> Option<Vec<u8>> {
+    if !matches!(value.len(), 32 | 64 | 128) || !value.bytes().all(|byte| byte.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect()
+}
+
+impl Image {
+    fn parse(path: &str, query: &[(String, String)], config: &Config) -> Result<Self, Response> {
+        match path.strip_prefix("/avatar/") {
+            Some(hash) => Self::parse_avatar(hash, query, config),
+            None => Self::parse_word(path, query, config),
+        }
+    }
+
+    fn parse_word(
+        path: &str,
+        query: &[(String, String)],
+        config: &Config,
+    ) -> Result<Self, Response> {
+        let not_found = || Response::text(404, "not found");
+        let path = path.strip_prefix('/').ok_or_else(not_found)?;
+        let (word, format) = if let Some(word) = path.strip_suffix(".png") {
+            (word, Format::Png)
+        } else if let Some(word) = path.strip_suffix(".svg") {
+            (word, Format::Svg)
+        } else {
+            return Err(not_found());
+        };
+        if word.is_empty() || word.contains('/') {
+            return Err(not_found());
+        }
+        let word = percent_decode(word, false)
+            .ok_or_else(|| Response::text(400, "invalid percent-encoding in path"))?;
+        let word = crate::normalization::normalize(&word, &config.normalize)
+            .map_err(|e| Response::text(400, &e.to_string()))?;
+
+        let mut image = Self {
+            seed: crate::generator::Seed::with_digest(word, config.hash, config.key.as_deref()),
+            format,
+            size: 10,
+            px: None,
+        };
+        for (name, value) in query {
+            match name.as_str() {
+                "size" => image.size = bounded(name, value, MAX_SIZE)?,
+                "px" => image.px = Some(bounded(name, value, MAX_PX)?),
+                _ => {}
+            }
+        }
+
+        Ok(image)
+    }
+
+    // follows the URLs of Gravatar, where the hash of the email address is used as the seed and
+    // `s` (or `size`) is the size of the image in pixels. The default image (`d`) and the other
+    // Gravatar parameters are ignored, as every hash has an identicon.
+    fn parse_avatar(
+        hash: &str,
+        query: &[(String, String)],
+        config: &Config,
+    ) -> Result<Self, Response> {
+        let hash = hash.strip_suffix(".png").unwrap_or(hash);
+        let digest = hex_digest(hash).ok_or_else(|| {
+            Response::text(
+                400,
+                "avatar hash must be the hex md5, sha256, sha512 or blake3 of an email",
+            )
+        })?;
+        let seed = match &config.key {
+            Some(key) => crate::generator::Seed::with_digest(digest, config.hash, Some(key)),
+            None => crate::generator::Seed::from_digest(digest).expect("digests are not empty"),
+        };
+
+        let mut image = Self {
+            seed,
+            format: Format::Png,
+            size: 10,
+            px: Some(80),
+        };
+        for (name, value) in query {
+            if name == "s" || name == "size" {
+                image.px = Some(bounded(name, value, MAX_PX)?);
+            }
+        }
+
+        Ok(image)
+    }
+
+    // the output only depends on these values, so that the tag can be computed without
+    // generating the image
+    fn etag(&self) -> String {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(format!(
+            "{}\n{}\n{:?}\n{}\n{:?}",
+            crate::generator::ALGORITHM_VERSION,
+            self.seed.hash(),
+            self.format,
+            self.size,
+            self.px
+        ));
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        format!("\"{}\"", digest)
+    }
+
+    fn render(&self) -> Result<(&'static str, Vec<u8>), crate::error::Error> {
+        let canva = crate::generator::Canva::new(self.size, self.seed.clone());
+        let mut content = std::io::Cursor::new(Vec::new());
+
+        let content_type = match self.format {
+            Format::Png => {
+                crate::rendering::Png {
+                    image_size: self.px,
+                    ..Default::default()
+                }
+                .render(&mut content, canva)?;
+                "image/png"
+            }
+            Format::Svg => {
+                crate::rendering::Svg::default().render(&mut content, canva)?;
+                "image/svg+xml"
+            }
+        };
+
+        Ok((content_type, content.into_inner()))
+    }
+}
+
+fn matches_etag(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag)
+}
+
+/// Computes the response to a request.
+pub fn respond(request: &Request, config: &Config) -> Response {
+    if request.method != "GET" && request.method != "HEAD" {
+        let mut response = Response::text(405, "method not allowed");
+        response.headers.push(("Allow", "GET, HEAD".to_string()));
+        return response;
+    }
+
+    let (path, query) = request
+        .target
+        .split_once('?')
+        .unwrap_or((request.target.as_str(), ""));
+    let query: Vec<(String, String)> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Some((percent_decode(name, true)?, percent_decode(value, true)?))
+        })
+        .collect();
+
+    let image = match Image::parse(path, &query, config) {
+        Ok(image) => image,
+        Err(response) => return response,
+    };
+
+    let etag = image.etag();
+    let cache_headers = vec![
+        ("ETag", etag.clone()),
+        (
+            "Cache-Control",
+            "public, max-age=31536000, immutable".to_string(),
+        ),
+    ];
+
+    if let Some(if_none_match) = request.header("If-None-Match") {
+        if matches_etag(if_none_match, &etag) {
+            return Response {
+                status: 304,
+                headers: cache_headers,
+                body: Vec::new(),
+            };
+        }
+    }
+
+    match image.render() {
+        Ok((content_type, body)) => {
+            let mut headers = vec![("Content-Type", content_type.to_string())];
+            headers.extend(cache_headers);
+
+            Response {
+                status: 200,
+                headers,
+                body,
+            }
+        }
+        Err(err) => Response::text(500, &err.to_string()),
+    }
+}
+
+pub struct Server {
+    listener: std::net::TcpListener,
+    config: std::sync::Arc<Config>,
+    workers: usize,
+}
+
+impl Server {
+    pub fn bind<A: std::net::ToSocketAddrs>(
+        address: A,
+        config: Config,
+    ) -> Result<Self, crate::error::Error> {
+        let listener = std::net::TcpListener::bind(address)?;
+        let workers = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+
+        Ok(Self {
+            listener,
+            config: std::sync::Arc::new(config),
+            workers,
+        })
+    }
+
+    /// Sets the number of connections answered at the same time, which defaults to the available
+    /// parallelism.
+    pub fn workers(mut self, workers: std::num::NonZeroUsize) -> Self {
+        self.workers = workers.get();
+        self
+    }
+
+    pub fn local_addr(&self) -> Result<std::net::SocketAddr, crate::error::Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Answers connections on a fixed number of worker threads. As many connections as there are
+    /// workers wait for one to be free, the others are answered with a 503 status.
+    pub fn serve(&self) -> Result<(), crate::error::Error> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(self.workers);
+        let receiver = std::sync::Arc::new(std::sync::Mutex::new(receiver));
+
+        (0..self.workers).for_each(|_| {
+            let receiver = receiver.clone();
+            let config = self.config.clone();
+
+            std::thread::spawn(move || loop {
+                // the lock is released as soon as a connection is received
+                let stream = receiver
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .recv();
+                let Ok(stream) = stream else {
+                    return;
+                };
+
+                if let Err(err) = Self::handle(stream, &config) {
+                    eprintln!("{}", err);
+                }
+            });
+        });
+
+        for stream in self.listener.incoming() {
+            // failing to accept a connection, for example when the client already reset it or
+            // when too many files are open, only drops that connection
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("cannot accept connection: {}", err);
+                    continue;
+                }
+            };
+
+            match sender.try_send(stream) {
+                Ok(()) => {}
+                Err(std::sync::mpsc::TrySendError::Full(stream)) => {
+                    let busy = Response::text(503, "too many connections");
+                    if let Err(err) = busy.write(&stream, true) {
+                        eprintln!("{}", err);
+                    }
+                }
+                Err(std::sync::mpsc::TrySendError::Disconnected(_)) => {
+                    return Err(crate::error::Error::generic(
+                        "no worker left to answer connections".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle(stream: std::net::TcpStream, config: &Config) -> Result<(), crate::error::Error> {
+        // clients which stop sending the request or reading the response must not keep the
+        // worker busy
+        stream.set_read_timeout(Some(std::time::Duration::from_secs(10)))?;
+        stream.set_write_timeout(Some(std::time::Duration::from_secs(10)))?;
+        let reader = std::io::BufReader::new(&stream);
+
+        match Request::read(reader) {
+            Ok(request) => respond(&request, config).write(&stream, request.method != "HEAD")?,
+            Err(_) => Response::text(400, "malformed request").write(&stream, true)?,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn request(target: &str) -> super::Request {
+        super::Request {
+            method: "GET".to_string(),
+            target: target.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    fn respond(request: &super::Request) -> super::Response {
+        super::respond(request, &super::Config::default())
+    }
+
+    #[test]
+    fn request_read() {
+        let input =
+            "GET /hello.png?size=5 HTTP/1.1\r\nHost: localhost\r\nIf-None-Match: \"abc\"\r\n\r\n";
+
+        let request = super::Request::read(input.as_bytes()).expect("failed to read request");
+
+        assert_eq!("GET", request.method);
+        assert_eq!("/hello.png?size=5", request.target);
+        assert_eq!(Some("\"abc\""), request.header("if-none-match"));
+        assert_eq!(Some("localhost"), request.header("Host"));
+        assert_eq!(None, request.header("Accept"));
+    }
+
+    #[test]
+    fn request_read_malformed() {
+        assert!(super::Request::read("GET /hello.png\r\n\r\n".as_bytes()).is_err());
+        assert!(super::Request::read("GET hello.png HTTP/1.1\r\n\r\n".as_bytes()).is_err());
+        assert!(super::Request::read("GET /hello.png HTTP/1.1\r\nHost".as_bytes()).is_err());
+        assert!(super::Request::read(
+            format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(20_000)).as_bytes()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn percent_decode() {
+        assert_eq!(
+            Some("hello world".to_string()),
+            super::percent_decode("hello%20world", false)
+        );
+        assert_eq!(Some("a+b".to_string()), super::percent_decode("a+b", false));
+        assert_eq!(Some("a b".to_string()), super::percent_decode("a+b", true));
+        assert_eq!(
+            Some("café".to_string()),
+            super::percent_decode("caf%C3%A9", false)
+        );
+        assert_eq!(None, super::percent_decode("%2", false));
+        assert_eq!(None, super::percent_decode("%zz", false));
+        assert_eq!(None, super::percent_decode("%ff", false));
+    }
+
+    #[test]
+    fn respond_png() {
+        let response = respond(&request("/hello.png?size=5"));
+
+        let expect =
+            std::fs::read("testdata/png_render.png").expect("failed to read expected image");
+        assert_eq!(200, response.status);
+        assert_eq!(Some("image/png"), response.header("Content-Type"));
+        assert_eq!(
+            Some("public, max-age=31536000, immutable"),
+            response.header("Cache-Control")
+        );
+        assert_eq!(expect, response.body);
+    }
+
+    #[test]
+    fn respond_png_px() {
+        let response = respond(&request("/hello.png?size=5&px=128"));
+
+        let img = image::load_from_memory(&response.body).expect("failed to decode PNG");
+        assert_eq!((128, 128), (img.width(), img.height()));
+    }
+
+    #[test]
+    fn respond_svg() {
+        let response = respond(&request("/hello.svg?size=5"));
+
+        let expect = include_bytes!("../testdata/svg_render.svg");
+        assert_eq!(200, response.status);
+        assert_eq!(Some("image/svg+xml"), response.header("Content-Type"));
+        assert_eq!(expect.to_vec(), response.body);
+    }
+
+    #[test]
+    fn respond_etag() {
+        let etag = |target| {
+            respond(&request(target))
+                .header("ETag")
+                .expect("missing etag")
+                .to_string()
+        };
+
+        assert_eq!(etag("/hello.png"), etag("/hello.png?size=10"));
+        assert_ne!(etag("/hello.png"), etag("/hello.svg"));
+        assert_ne!(etag("/hello.png"), etag("/hello.png?size=5"));
+        assert_ne!(etag("/hello.png"), etag("/hello.png?px=128"));
+        assert_ne!(etag("/hello.png"), etag("/world.png"));
+        assert!(!etag("/hello.png").starts_with("W/"));
+    }
+
+    #[test]
+    fn respond_not_modified() {
+        let etag = respond(&request("/hello.png"))
+            .header("ETag")
+            .expect("missing etag")
+            .to_string();
+        let mut conditional = request("/hello.png");
+        conditional
+            .headers
+            .push(("If-None-Match".to_string(), format!("\"other\", {}", etag)));
+
+        let response = respond(&conditional);
+
+        assert_eq!(304, response.status);
+        assert_eq!(Some(etag.as_str()), response.header("ETag"));
+        assert!(response.body.is_empty());
+    }
+
+    #[test]
+    fn respond_percent_encoded_word() {
+        let encoded = respond(&request("/hello%20world.svg"));
+        let expected = respond(&super::Request {
+            target: "/hello world.svg".to_string(),
+            ..request("")
+        });
+
+        assert_eq!(200, encoded.status);
+        assert_eq!(expected.body, encoded.body);
+    }
+
+    #[test]
+    fn respond_errors() {
+        let status = |target| respond(&request(target)).status;
+
+        assert_eq!(404, status("/"));
+        assert_eq!(404, status("/hello.gif"));
+        assert_eq!(404, status("/a/hello.png"));
+        assert_eq!(404, status("/.png"));
+        assert_eq!(400, status("/hello.png?size=0"));
+        assert_eq!(400, status("/hello.png?size=1000"));
+        assert_eq!(400, status("/hello.png?px=abc"));
+        assert_eq!(400, status("/hello%zz.png"));
+
+        let post = respond(&super::Request {
+            method: "POST".to_string(),
+            ..request("/hello.png")
+        });
+        assert_eq!(405, post.status);
+        assert_eq!(Some("GET, HEAD"), post.header("Allow"));
+    }
+
+    #[test]
+    fn respond_avatar() {
+        // md5 of "test@example.com"
+        let response = respond(&request(
+            "/avatar/55502f40dc8b7c769880b10874abc9d0?s=120&d=identicon",
+        ));
+
+        let img = image::load_from_memory(&response.body).expect("failed to decode PNG");
+        assert_eq!(200, response.status);
+        assert_eq!(Some("image/png"), response.header("Content-Type"));
+        assert_eq!((120, 120), (img.width(), img.height()));
+    }
+
+    #[test]
+    fn respond_avatar_default_size() {
+        let response = respond(&request(
+            "/avatar/973dfe463ec85785f5f95af5ba3906eedb2d931c24e69824a89ea65dba4e813b",
+        ));
+
+        let img = image::load_from_memory(&response.body).expect("failed to decode PNG");
+        assert_eq!(200, response.status);
+        assert_eq!((80, 80), (img.width(), img.height()));
+    }
+
+    #[test]
+    fn respond_avatar_hash_as_seed() {
+        let etag = |target: String| {
+            respond(&request(&target))
+                .header("ETag")
+                .expect("missing etag")
+                .to_string()
+        };
+        let hash = "55502f40dc8b7c769880b10874abc9d0";
+
+        assert_eq!(
+            etag(format!("/avatar/{}", hash)),
+            etag(format!("/avatar/{}.png?size=80", hash.to_uppercase()))
+        );
+        assert_ne!(
+            etag(format!("/avatar/{}", hash)),
+            etag(format!("/{}.png?px=80", hash))
+        );
+        assert_ne!(
+            etag(format!("/avatar/{}", hash)),
+            etag(format!("/avatar/{}?s=81", hash))
+        );
+    }
+
+    #[test]
+    fn respond_avatar_errors() {
+        let status = |target| respond(&request(target)).status;
+
+        assert_eq!(400, status("/avatar/"));
+        assert_eq!(400, status("/avatar/test@example.com"));
+        assert_eq!(400, status("/avatar/55502f40dc8b7c769880b10874abc9d"));
+        assert_eq!(400, status("/avatar/+5502f40dc8b7c769880b10874abc9d0"));
+        assert_eq!(400, status("/avatar/55502f40dc8b7c769880b10874abc9zz"));
+        assert_eq!(400, status("/avatar/55502f40dc8b7c769880b10874abc9d0?s=0"));
+        assert_eq!(
+            400,
+            status("/avatar/55502f40dc8b7c769880b10874abc9d0?s=5000")
+        );
+    }
+
+    #[test]
+    fn respond_keyed() {
+        let keyed = |target, key: &[u8]| {
+            super::respond(
+                &request(target),
+                &super::Config {
+                    key: Some(key.to_vec()),
+                    ..Default::default()
+                },
+            )
+        };
+        let avatar = "/avatar/55502f40dc8b7c769880b10874abc9d0";
+
+        let expect = crate::generator::Seed::keyed("hello", b"secret");
+        let canva = crate::generator::Canva::new(5, expect);
+        let mut svg = Vec::new();
+        crate::rendering::Svg::default()
+            .render(&mut svg, canva)
+            .expect("failed to render SVG");
+        assert_eq!(svg, keyed("/hello.svg?size=5", b"secret").body);
+
+        assert_ne!(
+            respond(&request("/hello.png")).header("ETag"),
+            keyed("/hello.png", b"secret").header("ETag")
+        );
+        assert_ne!(
+            keyed("/hello.png", b"secret").header("ETag"),
+            keyed("/hello.png", b"other").header("ETag")
+        );
+        assert_ne!(
+            respond(&request(avatar)).body,
+            keyed(avatar, b"secret").body
+        );
+    }
+
+    #[test]
+    fn respond_normalized() {
+        let config = super::Config {
+            normalize: vec![
+                crate::normalization::Normalization::Trim,
+                crate::normalization::Normalization::Email,
+            ],
+            ..Default::default()
+        };
+
+        let expect = respond(&request("/alice@gmail.com.png"));
+        let actual = super::respond(&request("/%20A.Lice+News@GMail.com.png"), &config);
+        assert_eq!(200, actual.status);
+        assert_eq!(expect.body, actual.body);
+        assert_eq!(expect.header("ETag"), actual.header("ETag"));
+
+        let invalid = super::respond(&request("/alice.png"), &config);
+        assert_eq!(400, invalid.status);
+        assert_eq!(b"invalid email 'alice'\n".to_vec(), invalid.body);
+    }
+
+    #[test]
+    fn respond_avatar_matches_hash_function() {
+        let config = super::Config {
+            hash: crate::generator::HashFunction::Md5,
+            ..Default::default()
+        };
+
+        // md5 of "hello"
+        let avatar = super::respond(
+            &request("/avatar/5d41402abc4b2a76b9719d911017c592"),
+            &config,
+        );
+        let word = super::respond(&request("/hello.png?px=80"), &config);
+
+        assert_eq!(200, avatar.status);
+        assert_eq!(word.body, avatar.body);
+        assert_ne!(respond(&request("/hello.png?px=80")).body, avatar.body);
+
+        let sha512 = crate::generator::Seed::with_digest(
+            "hello",
+            crate::generator::HashFunction::Sha512,
+            None,
+        );
+        assert_eq!(
+            200,
+            respond(&request(&format!("/avatar/{}", sha512.hash()))).status
+        );
+    }
+
+    fn exchange(address: std::net::SocketAddr, request: &str) -> String {
+        use std::io::{Read, Write};
+
+        let mut stream = std::net::TcpStream::connect(address).expect("failed to connect");
+        stream
+            .write_all(request.as_bytes())
+            .expect("failed to send request");
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .expect("failed to read response");
+
+        String::from_utf8_lossy(&response).to_string()
+    }
+
+    #[test]
+    fn server_serve() {
+        let server = super::Server::bind("127.0.0.1:0", super::Config::default())
+            .expect("failed to bind server");
+        let address = server.local_addr().expect("failed to read address");
+        std::thread::spawn(move || server.serve());
+
+        let response = exchange(
+            address,
+            "GET /hello.svg?size=5 HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        );
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .expect("missing end of headers");
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(head.contains("Content-Type: image/svg+xml\r\n"));
+        assert!(head.contains(&format!("Content-Length: {}\r\n", body.len())));
+        assert_eq!(include_str!("../testdata/svg_render.svg"), body);
+
+        let etag = head
+            .lines()
+            .find_map(|line| line.strip_prefix("ETag: "))
+            .expect("missing etag");
+        let not_modified = exchange(
+            address,
+            &format!(
+                "GET /hello.svg?size=5 HTTP/1.1\r\nIf-None-Match: {}\r\n\r\n",
+                etag
+            ),
+        );
+        assert!(not_modified.starts_with("HTTP/1.1 304 Not Modified\r\n"));
+        assert!(not_modified.ends_with("\r\n\r\n"));
+
+        let head = exchange(address, "HEAD /hello.svg HTTP/1.1\r\n\r\n");
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(head.ends_with("\r\n\r\n"));
+
+        let malformed = exchange(address, "hello\r\n\r\n");
+        assert!(malformed.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    }
+
+    #[test]
+    fn server_serve_rejects_busy() {
+        use std::io::Read;
+
+        let server = super::Server::bind("127.0.0.1:0", super::Config::default())
+            .expect("failed to bind server")
+            .workers(std::num::NonZeroUsize::MIN);
+        let address = server.local_addr().expect("failed to read address");
+        std::thread::spawn(move || server.serve());
+
+        // connections sending nothing keep the worker busy and fill the queue, until the next
+        // ones are rejected right away
+        let mut idle = Vec::new();
+        let rejected = (0..10).find_map(|_| {
+            let mut stream = std::net::TcpStream::connect(address).expect("failed to connect");
+            stream
+                .set_read_timeout(Some(std::time::Duration::from_millis(200)))
+                .expect("failed to set timeout");
+            let mut response = Vec::new();
+            match stream.read_to_end(&mut response) {
+                Ok(_) => Some(String::from_utf8_lossy(&response).to_string()),
+                Err(_) => {
+                    idle.push(stream);
+                    None
+                }
+            }
+        });
+
+        let rejected = rejected.expect("no connection was rejected");
+        assert!(rejected.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+        assert!(rejected.ends_with("too many connections\n"));
+        assert!(idle.len() <= 2);
+    }
+}